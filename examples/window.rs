@@ -2,12 +2,12 @@ extern crate minifb;
 extern crate viewdock;
 
 use minifb::{Key, WindowOptions, MouseMode, MouseButton};
-use viewdock::{Workspace, Split, Rect, ViewHandle, Direction, SplitHandle};
+use viewdock::{Workspace, IntRect, Rect, ViewHandle, Direction, SplitHandle};
 
 const WIDTH: usize = 1024;
 const HEIGHT: usize = 768;
 
-fn fill_rect(dest: &mut [u32], rect: Rect, color: u32) {
+fn fill_rect(dest: &mut [u32], rect: IntRect, color: u32) {
     let x0 = rect.x as usize;
     let y0 = rect.y as usize;
     let x1 = x0 + rect.width as usize;
@@ -20,30 +20,9 @@ fn fill_rect(dest: &mut [u32], rect: Rect, color: u32) {
     }
 }
 
-fn draw_recrusive(dest: &mut [u32], split: &Split) {
-
-    //println!("Ratio {}", split.ratio);
-
-    for view in &split.left_views.views {
-        fill_rect(dest, view.rect, view.handle.0 as u32);
-    }
-
-    for view in &split.right_views.views {
-        fill_rect(dest, view.rect, view.handle.0 as u32);
-    }
-
-    if let Some(ref split) = split.left {
-        draw_recrusive(dest, split); 
-    }
-
-    if let Some(ref split) = split.right {
-        draw_recrusive(dest, split); 
-    }
-}
-
 fn draw_ws(dest: &mut [u32], ws: &Workspace) {
-    if let Some(ref split) = ws.split {
-        draw_recrusive(dest, split);
+    for (handle, rect) in ws.int_rects() {
+        fill_rect(dest, rect, handle.0 as u32);
     }
 }
 
@@ -57,12 +36,13 @@ fn main() {
     let mut prev_mouse: (f32, f32) = (0.0, 0.0);
     let mut state = State::Def;
     let mut handle = SplitHandle(0);
+    let mut first_frame = true;
 
-    let mut ws = Workspace::new(Rect::new(0.0, 0.0, WIDTH as f32, HEIGHT as f32)).unwrap();
+    let mut ws: Workspace = Workspace::new(Rect::new(0.0, 0.0, WIDTH as f32, HEIGHT as f32)).unwrap();
 
-    ws.split_top(ViewHandle(0xff), Direction::Vertical);
-    ws.split_top(ViewHandle(0xff00ff), Direction::Vertical);
-    ws.split_by_view_handle(Direction::Vertical, ViewHandle(0xff00ff), ViewHandle(0x00ff00));
+    ws.split_top(ViewHandle(0xff), Direction::Vertical).unwrap();
+    ws.split_top(ViewHandle(0xff00ff), Direction::Vertical).unwrap();
+    ws.split_by_view_handle(Direction::Vertical, ViewHandle(0xff00ff), ViewHandle(0x00ff00)).unwrap();
     //ws.split_by_view_handle(Direction::Vertical, ViewHandle(0x00ff00), ViewHandle(0x002255));
     //ws.split_by_view_handle(Direction::Horizontal, ViewHandle(0x00ff00), ViewHandle(0x5522));
     ws.update();
@@ -75,10 +55,12 @@ fn main() {
 
     while window.is_open() && !window.is_key_down(Key::Escape) {
 
-        ws.update();
-
-        //fill_rect(&mut buffer, Rect::new(0.0, 0.0, WIDTH as f32, HEIGHT as f32), 0);
-        draw_ws(&mut buffer, &ws);
+        // Only redraw when a view has moved since the last frame
+        if !ws.update().is_empty() || first_frame {
+            //fill_rect(&mut buffer, Rect::new(0.0, 0.0, WIDTH as f32, HEIGHT as f32), 0);
+            draw_ws(&mut buffer, &ws);
+            first_frame = false;
+        }
 
         let mouse_pos = window.get_mouse_pos(MouseMode::Clamp).unwrap();
 
@@ -96,8 +78,9 @@ fn main() {
                 let delta = (prev_mouse.0 - mouse_pos.0, prev_mouse.1 - mouse_pos.1);
 
                 if window.get_mouse_down(MouseButton::Left) {
-                    ws.drag_sizer(handle, delta);
+                    ws.drag_sizer(handle, delta).unwrap();
                 } else {
+                    ws.end_sizer_drag(handle).unwrap();
                     handle = SplitHandle(0);
                     state = State::Def;
                 }
@@ -108,7 +91,7 @@ fn main() {
             let delta = (mouse_pos.0 - prev_mouse.0, mouse_pos.1 - prev_mouse.1);
 
             if window.get_mouse_down(MouseButton::Left) {
-                ws.drag_sizer(handle, delta);
+                ws.drag_sizer(handle, delta).unwrap();
             }
         }
 
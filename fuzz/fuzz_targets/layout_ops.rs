@@ -0,0 +1,17 @@
+//! Run with `cargo fuzz run layout_ops` from the repository root.
+
+#![no_main]
+#[macro_use] extern crate libfuzzer_sys;
+extern crate viewdock;
+
+#[path = "../../tests/ops/mod.rs"]
+mod ops;
+
+fuzz_target!(|data: &[u8]| {
+    let mut harness = ops::Harness::new();
+
+    for op in ops::decode(data) {
+        harness.apply(op);
+        harness.check();
+    }
+});
@@ -0,0 +1,280 @@
+use std::collections::HashMap;
+use std::mem;
+use {Rect, ViewHandle, Workspace};
+
+/// How the progress of a transition is shaped over time
+#[derive(Debug, Clone, Copy)]
+pub enum Easing {
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+    /// Maps the linear progress in [0, 1] to the eased one, should return 0 for 0 and 1 for 1
+    Custom(fn(f32) -> f32),
+}
+
+impl Easing {
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOut => if t < 0.5 { 4.0 * t * t * t } else { 1.0 - (-2.0 * t + 2.0).powi(3) * 0.5 },
+            Easing::Custom(f) => f(t),
+        }
+    }
+}
+
+/// Animation of view rects in `Workspace::update` and `Workspace::update_animated`, disabled when
+/// `Workspace::animation` is None
+#[derive(Debug, Clone, Copy)]
+pub struct Animation {
+    /// Length of a transition in seconds
+    pub duration: f32,
+    pub easing: Easing,
+}
+
+impl Default for Animation {
+    fn default() -> Animation {
+        Animation {
+            duration: 0.2,
+            easing: Easing::EaseOut,
+        }
+    }
+}
+
+/// A view moving from one rect to another
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Transition {
+    from: Rect,
+    to: Rect,
+    /// Rect reported after the last step
+    current: Rect,
+    elapsed: f32,
+}
+
+fn lerp_rect(from: Rect, to: Rect, t: f32) -> Rect {
+    let lerp = |a: f32, b: f32| a + (b - a) * t;
+    Rect::new(lerp(from.x, to.x), lerp(from.y, to.y), lerp(from.width, to.width), lerp(from.height, to.height))
+}
+
+impl<T> Workspace<T> {
+    ///
+    /// Same as `update` but moves the rect of every view, docked or floating, towards its new
+    /// position over `Animation::duration` instead of jumping there. `dt` is the time in seconds
+    /// since the last call. The tree, containers, headers and tabs change at once, only `View::rect`
+    /// is interpolated. A view that gets a new target while moving starts from where it is. New
+    /// views appear at their place right away. Returns the views whose rect, tab or close button
+    /// moved, use `is_animating` to know if another call is needed.
+    ///
+    pub fn update_animated(&mut self, dt: f32) -> Vec<ViewHandle> {
+        let mut old = mem::take(&mut self.transitions);
+
+        let animation = match self.animation {
+            Some(animation) if animation.duration > 0.0 => animation,
+            _ => return self.finish_transitions(old),
+        };
+
+        // Views of splits that aren't laid out again keep their rect, which has to be the target.
+        // Views that weren't moving start from where they are.
+        let mut before = HashMap::new();
+
+        self.for_each_view_mut(&mut |view| {
+            match old.get(&view.handle) {
+                Some(transition) => view.rect = transition.to,
+                None => { before.insert(view.handle, view.rect); }
+            }
+        });
+
+        let mut changed = self.lay_out();
+
+        let pixel_exact = self.pixel_exact;
+        let mut transitions = HashMap::new();
+
+        self.for_each_view_mut(&mut |view| {
+            let target = view.rect;
+
+            let mut transition = match old.remove(&view.handle) {
+                Some(transition) if transition.to == target => transition,
+                Some(transition) => {
+                    Transition { from: transition.current, to: target, current: transition.current, elapsed: 0.0 }
+                }
+                // Views that haven't been laid out before appear at their place right away
+                None => match before.get(&view.handle) {
+                    Some(&rect) if rect != Rect::default() && rect != target => {
+                        Transition { from: rect, to: target, current: rect, elapsed: 0.0 }
+                    }
+                    _ => Transition { from: target, to: target, current: target, elapsed: animation.duration },
+                },
+            };
+
+            let previous = transition.current;
+
+            transition.elapsed = (transition.elapsed + dt.max(0.0)).min(animation.duration);
+
+            let t = animation.easing.apply(transition.elapsed / animation.duration);
+            let mut rect = lerp_rect(transition.from, transition.to, t);
+
+            if pixel_exact {
+                rect = rect.round();
+            }
+
+            transition.current = rect;
+            view.rect = rect;
+
+            if rect != previous && !changed.contains(&view.handle) {
+                changed.push(view.handle);
+            }
+
+            // Finished transitions are kept so the next target change starts from here
+            transitions.insert(view.handle, transition);
+        });
+
+        self.transitions = transitions;
+
+        changed
+    }
+
+    /// Lays out without animation, views that were still moving jump to their target
+    fn finish_transitions(&mut self, old: HashMap<ViewHandle, Transition>) -> Vec<ViewHandle> {
+        if !old.is_empty() {
+            self.for_each_view_mut(&mut |view| {
+                if let Some(transition) = old.get(&view.handle) {
+                    view.rect = transition.to;
+                }
+            });
+        }
+
+        let mut changed = self.lay_out();
+
+        for (handle, transition) in old {
+            if transition.current != transition.to && !changed.contains(&handle) && self.view(handle).is_some() {
+                changed.push(handle);
+            }
+        }
+
+        changed
+    }
+
+    /// True while a view is moving, apps should keep calling `update_animated` and redrawing
+    pub fn is_animating(&self) -> bool {
+        match self.animation {
+            Some(animation) => self.transitions.values().any(|transition| transition.elapsed < animation.duration),
+            None => false,
+        }
+    }
+
+    /// Rect the view is moving to, the same as its rect from `update`
+    pub fn target_rect(&self, handle: ViewHandle) -> Option<Rect> {
+        match self.transitions.get(&handle) {
+            Some(transition) => Some(transition.to),
+            None => self.view(handle).map(|view| view.rect),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod test {
+    use test_util::{check_range, sorted};
+    use {Animation, Direction, Easing, Rect, ViewHandle, Workspace};
+
+    #[test]
+    fn test_easing() {
+        for easing in &[Easing::Linear, Easing::EaseIn, Easing::EaseOut, Easing::EaseInOut] {
+            assert_eq!(easing.apply(0.0), 0.0);
+            assert_eq!(check_range(easing.apply(1.0), 1.0, 0.0001), true);
+        }
+
+        assert_eq!(check_range(Easing::EaseInOut.apply(0.5), 0.5, 0.0001), true);
+        assert_eq!(Easing::Custom(|t| t * t).apply(0.5), 0.25);
+    }
+
+    #[test]
+    fn test_view_moves_over_duration() {
+        let mut ws: Workspace = Workspace::new(Rect::new(0.0, 0.0, 1000.0, 500.0)).unwrap();
+        ws.animation = Some(Animation { duration: 1.0, easing: Easing::Linear });
+
+        ws.split_top(ViewHandle(1), Direction::Vertical).unwrap();
+        ws.update_animated(0.016);
+        assert_eq!(ws.is_animating(), false);
+        assert_eq!(ws.view(ViewHandle(1)).unwrap().rect.width, 1000.0);
+
+        // The new view appears at once while the old one shrinks from 1000 to 500
+        ws.split_top(ViewHandle(2), Direction::Vertical).unwrap();
+        assert_eq!(sorted(ws.update_animated(0.25)), vec![1, 2]);
+        assert_eq!(ws.is_animating(), true);
+        assert_eq!(check_range(ws.view(ViewHandle(1)).unwrap().rect.width, 875.0, 0.01), true);
+        assert_eq!(ws.view(ViewHandle(2)).unwrap().rect.width, 500.0);
+        assert_eq!(ws.target_rect(ViewHandle(1)).unwrap().width, 500.0);
+
+        // The containers are already at their new place
+        assert_eq!(ws.split.as_ref().unwrap().right_views().rect.width, 500.0);
+
+        assert_eq!(ws.update_animated(0.5), vec![ViewHandle(1)]);
+        assert_eq!(check_range(ws.view(ViewHandle(1)).unwrap().rect.width, 625.0, 0.01), true);
+
+        assert_eq!(ws.update_animated(0.5), vec![ViewHandle(1)]);
+        assert_eq!(ws.view(ViewHandle(1)).unwrap().rect.width, 500.0);
+        assert_eq!(ws.is_animating(), false);
+        assert_eq!(ws.update_animated(0.5).is_empty(), true);
+    }
+
+    #[test]
+    fn test_retarget_starts_from_current_rect() {
+        let mut ws: Workspace = Workspace::new(Rect::new(0.0, 0.0, 1000.0, 500.0)).unwrap();
+        ws.animation = Some(Animation { duration: 1.0, easing: Easing::Linear });
+
+        ws.split_top(ViewHandle(1), Direction::Vertical).unwrap();
+        ws.split_top(ViewHandle(2), Direction::Vertical).unwrap();
+        ws.update_animated(1.0);
+
+        let handle = ws.split.as_ref().unwrap().handle;
+        ws.split.as_mut().unwrap().set_ratio(0.25);
+        ws.update_animated(0.5);
+        assert_eq!(check_range(ws.view(ViewHandle(1)).unwrap().rect.width, 375.0, 0.01), true);
+
+        // Halfway there the sizer moves back, view 1 turns around at 375
+        ws.reset_sizer(handle).unwrap();
+        ws.update_animated(0.5);
+        assert_eq!(check_range(ws.view(ViewHandle(1)).unwrap().rect.width, 437.5, 0.01), true);
+
+        ws.animation = None;
+        assert_eq!(sorted(ws.update_animated(0.1)), vec![1, 2]);
+        assert_eq!(ws.is_animating(), false);
+        assert_eq!(ws.view(ViewHandle(1)).unwrap().rect.width, 500.0);
+    }
+
+    #[test]
+    fn test_update_starts_transitions() {
+        let mut ws: Workspace = Workspace::new(Rect::new(0.0, 0.0, 1000.0, 500.0)).unwrap();
+        ws.split_top(ViewHandle(1), Direction::Vertical).unwrap();
+        ws.update();
+
+        // `update` starts moving the view without advancing the time
+        ws.animation = Some(Animation { duration: 1.0, easing: Easing::Linear });
+        ws.split_top(ViewHandle(2), Direction::Vertical).unwrap();
+        assert_eq!(sorted(ws.update()), vec![1, 2]);
+        assert_eq!(ws.view(ViewHandle(1)).unwrap().rect.width, 1000.0);
+        assert_eq!(ws.is_animating(), true);
+
+        ws.update_animated(0.5);
+        assert_eq!(check_range(ws.view(ViewHandle(1)).unwrap().rect.width, 750.0, 0.01), true);
+    }
+
+    #[test]
+    fn test_floating_views_move() {
+        let mut ws: Workspace = Workspace::new(Rect::new(0.0, 0.0, 1000.0, 500.0)).unwrap();
+        ws.animation = Some(Animation { duration: 1.0, easing: Easing::Linear });
+        ws.split_top(ViewHandle(1), Direction::Vertical).unwrap();
+        ws.split_top(ViewHandle(2), Direction::Vertical).unwrap();
+        ws.float_view(ViewHandle(2), Rect::new(100.0, 100.0, 200.0, 200.0)).unwrap();
+        ws.update_animated(1.0);
+
+        ws.move_floating(ViewHandle(2), Rect::new(300.0, 100.0, 200.0, 200.0)).unwrap();
+        assert_eq!(ws.update_animated(0.5), vec![ViewHandle(2)]);
+        assert_eq!(check_range(ws.view(ViewHandle(2)).unwrap().rect.x, 200.0, 0.01), true);
+    }
+}
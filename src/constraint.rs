@@ -0,0 +1,426 @@
+use std::collections::HashMap;
+use solver::{self, Op, Row};
+use {Container, Direction, DockArea, Error, Rect, Result, Split, SplitHandle, ViewHandle, Workspace};
+
+/// How hard the layout tries to meet a constraint, `Required` ones are never broken
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Strength {
+    Required,
+    Strong,
+    Medium,
+    Weak,
+}
+
+impl Strength {
+    /// Cost per pixel of missing the constraint, None for required constraints
+    fn weight(self) -> Option<f64> {
+        match self {
+            Strength::Required => None,
+            Strength::Strong => Some(1_000_000.0),
+            Strength::Medium => Some(1_000.0),
+            Strength::Weak => Some(1.0),
+        }
+    }
+}
+
+/// Splits keep their weights unless a constraint says otherwise, this is weaker than `Strength::Weak`
+const STAY_WEIGHT: f64 = 0.001;
+
+/// A size in logical units or relative to the split the container is in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Length {
+    Pixels(f32),
+    /// 0.0 - 100.0 of the width or height of the split holding the container
+    Percent(f32),
+}
+
+/// Limits of the width or height of the container holding a view
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SizeConstraint {
+    /// Minimum size in logical units, 0.0 for none
+    pub min: f32,
+    /// Maximum size in logical units, infinity for none
+    pub max: f32,
+    /// Strength of `min` and `max`
+    pub strength: Strength,
+    pub preferred: Option<Length>,
+    pub preferred_strength: Strength,
+}
+
+impl Default for SizeConstraint {
+    fn default() -> SizeConstraint {
+        SizeConstraint {
+            min: 0.0,
+            max: f32::INFINITY,
+            strength: Strength::Strong,
+            preferred: None,
+            preferred_strength: Strength::Medium,
+        }
+    }
+}
+
+/// Constraints of a view, see `Workspace::set_constraints`
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ViewConstraints {
+    pub width: SizeConstraint,
+    pub height: SizeConstraint,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SizeAxis {
+    Width,
+    Height,
+}
+
+/// Views whose containers try to stay the same width or height, see `Workspace::add_equal_sizes`
+#[derive(Debug, Clone, PartialEq)]
+pub struct EqualSizes {
+    pub axis: SizeAxis,
+    pub views: Vec<ViewHandle>,
+    pub strength: Strength,
+}
+
+/// Sum of the split variables times their factors plus a constant
+#[derive(Debug, Clone, Default)]
+struct Expr {
+    constant: f64,
+    terms: Vec<(usize, f64)>,
+}
+
+impl Expr {
+    fn constant(constant: f64) -> Expr {
+        Expr { constant, terms: Vec::new() }
+    }
+
+    fn var(var: usize) -> Expr {
+        Expr { constant: 0.0, terms: vec![(var, 1.0)] }
+    }
+
+    fn scaled(&self, factor: f64) -> Expr {
+        Expr {
+            constant: self.constant * factor,
+            terms: self.terms.iter().map(|&(var, value)| (var, value * factor)).collect(),
+        }
+    }
+
+    fn sub(&self, other: &Expr) -> Expr {
+        let mut expr = self.clone();
+        expr.constant -= other.constant;
+        expr.terms.extend(other.terms.iter().map(|&(var, value)| (var, -value)));
+        expr
+    }
+
+    fn value(&self, values: &[f64]) -> f64 {
+        self.terms.iter().fold(self.constant, |sum, &(var, factor)| sum + values[var] * factor)
+    }
+}
+
+/// Linear problem built from the split trees. Every split gets a variable for the size of each of
+/// its children but the last, all container sizes are linear in those.
+#[derive(Default)]
+struct Model {
+    /// Split, its size along its direction and its first variable. A split with n children has
+    /// n - 1 variables, the last child gets what is left
+    splits: Vec<(SplitHandle, Expr, usize)>,
+    /// Number of split variables
+    vars: usize,
+    /// Width and height of the container holding each view
+    sizes: HashMap<ViewHandle, (Expr, Expr)>,
+    /// Constraints as `expr op 0` with their weight, None for required
+    constraints: Vec<(Expr, Op, Option<f64>)>,
+}
+
+impl Model {
+    fn add(&mut self, expr: Expr, op: Op, strength: Strength) {
+        self.constraints.push((expr, op, strength.weight()));
+    }
+
+    fn add_size(&mut self, size: &Expr, parent: &Expr, constraint: &SizeConstraint, scale: f64) {
+        if constraint.min > 0.0 {
+            self.add(size.sub(&Expr::constant(constraint.min as f64 * scale)), Op::Ge, constraint.strength);
+        }
+
+        if constraint.max.is_finite() {
+            self.add(size.sub(&Expr::constant(constraint.max as f64 * scale)), Op::Le, constraint.strength);
+        }
+
+        let preferred = match constraint.preferred {
+            Some(Length::Pixels(pixels)) => Expr::constant(pixels as f64 * scale),
+            Some(Length::Percent(percent)) => parent.scaled(percent as f64 / 100.0),
+            None => return,
+        };
+
+        self.add(size.sub(&preferred), Op::Eq, constraint.preferred_strength);
+    }
+
+    fn add_container<T>(&mut self, container: &Container<T>, size: (&Expr, &Expr), parent: (&Expr, &Expr), scale: f64) {
+        // The views of a grid only get a cell, which the grid tracks size
+        if container.grid.is_some() {
+            return;
+        }
+
+        for view in &container.views {
+            self.add_size(size.0, parent.0, &view.constraints.width, scale);
+            self.add_size(size.1, parent.1, &view.constraints.height, scale);
+            self.sizes.insert(view.handle, (size.0.clone(), size.1.clone()));
+        }
+    }
+
+    fn add_split<T>(&mut self, split: &Split<T>, width: Expr, height: Expr, scale: f64) {
+        let extent = match split.direction {
+            Direction::Full => {
+                self.add_container(split.left_views(), (&width, &height), (&width, &height), scale);
+                return;
+            }
+            Direction::Vertical => width.clone(),
+            Direction::Horizontal => height.clone(),
+        };
+
+        let first = self.vars;
+        self.vars += split.children.len() - 1;
+        self.splits.push((split.handle, extent.clone(), first));
+
+        // Without other constraints the weights stay as they are
+        let mut sizes: Vec<Expr> = split.children[..split.children.len() - 1].iter().enumerate().map(|(index, child)| {
+            let var = Expr::var(first + index);
+            self.constraints.push((var.sub(&extent.scaled(child.weight as f64)), Op::Eq, Some(STAY_WEIGHT)));
+            var
+        }).collect();
+
+        // Every child has to fit
+        let rest = sizes.iter().fold(extent, |rest, size| rest.sub(size));
+        self.constraints.push((rest.clone(), Op::Ge, None));
+        sizes.push(rest);
+
+        for (child, size) in split.children.iter().zip(sizes) {
+            let size = match split.direction {
+                Direction::Vertical => (size, height.clone()),
+                _ => (width.clone(), size),
+            };
+
+            match child.split {
+                Some(ref child) => self.add_split(child, size.0, size.1, scale),
+                None => self.add_container(&child.views, (&size.0, &size.1), (&width, &height), scale),
+            }
+        }
+    }
+
+    /// Solves the model, returns the value of every split variable
+    fn solve(&self) -> Option<Vec<f64>> {
+        let vars = self.vars;
+        let mut cost = vec![0.0; vars];
+        let mut rows = Vec::new();
+        let mut next = vars;
+
+        // Constraints that may be broken get error variables which cost their weight
+        for (expr, op, weight) in &self.constraints {
+            let mut coeffs = expr.terms.clone();
+
+            if let Some(weight) = *weight {
+                if *op != Op::Le {
+                    coeffs.push((next, 1.0));
+                    cost.push(weight);
+                    next += 1;
+                }
+
+                if *op != Op::Ge {
+                    coeffs.push((next, -1.0));
+                    cost.push(weight);
+                    next += 1;
+                }
+            }
+
+            rows.push(Row { coeffs, op: *op, rhs: -expr.constant });
+        }
+
+        solver::minimize(next, &cost, &rows).map(|mut values| {
+            values.truncate(vars);
+            values
+        })
+    }
+}
+
+impl<T> Workspace<T> {
+    ///
+    /// Sets the min, max and preferred size of the container holding the view. With the
+    /// `constraints` feature `update` solves the weights of all splits so the constraints are met as
+    /// well as their strengths allow. Splits keep their weights where no constraint applies. The
+    /// constraints of views in a grid are ignored, their cells are sized by the tracks of the grid
+    /// and the same goes for `add_equal_sizes`.
+    ///
+    pub fn set_constraints(&mut self, handle: ViewHandle, constraints: ViewConstraints) -> Result<()> {
+        let view = self.view_mut(handle).ok_or(Error::ViewNotFound(handle))?;
+        view.constraints = constraints;
+
+        self.mark_dirty();
+
+        Ok(())
+    }
+
+    /// Keeps the width or height of the containers holding the views the same, for example for columns
+    pub fn add_equal_sizes(&mut self, axis: SizeAxis, views: &[ViewHandle], strength: Strength) -> Result<()> {
+        if let Some(&handle) = views.iter().find(|&&handle| self.view(handle).is_none()) {
+            return Err(Error::ViewNotFound(handle));
+        }
+
+        self.equal_sizes.push(EqualSizes { axis, views: views.to_vec(), strength });
+        self.mark_dirty();
+
+        Ok(())
+    }
+
+    pub fn equal_sizes(&self) -> &[EqualSizes] {
+        &self.equal_sizes
+    }
+
+    pub fn clear_equal_sizes(&mut self) {
+        self.equal_sizes.clear();
+        self.mark_dirty();
+    }
+
+    fn has_constraints(&self) -> bool {
+        !self.equal_sizes.is_empty() ||
+            self.views().any(|(view, _)| view.constraints != ViewConstraints::default())
+    }
+
+    /// True if nothing that the solved weights depend on has changed since the last `update`
+    fn is_laid_out(&self, rects: &[Rect; 5]) -> bool {
+        // A clean root has nothing dirty below it
+        self.layout_key == Some((self.metrics(), self.pixel_exact)) &&
+            DockArea::ALL.iter().zip(rects.iter())
+                .all(|(&area, &rect)| self.tree(area).as_ref().is_none_or(|split| !split.is_dirty() && split.rect == rect))
+    }
+
+    ///
+    /// Sets the weights of every split from the constraints of the views, called by `update`. The
+    /// weights stay as they are if the required constraints can't all be met.
+    ///
+    pub(crate) fn solve_constraints(&mut self, rects: &[Rect; 5]) {
+        if !self.has_constraints() || self.is_laid_out(rects) {
+            return;
+        }
+
+        let scale = self.scale() as f64;
+        let mut model = Model::default();
+
+        for (&area, rect) in DockArea::ALL.iter().zip(rects.iter()) {
+            if let Some(ref split) = *self.tree(area) {
+                model.add_split(split, Expr::constant(rect.width as f64), Expr::constant(rect.height as f64), scale);
+            }
+        }
+
+        for group in &self.equal_sizes {
+            let sizes: Vec<&Expr> = group.views.iter()
+                .filter_map(|handle| model.sizes.get(handle))
+                .map(|size| if group.axis == SizeAxis::Width { &size.0 } else { &size.1 })
+                .collect();
+
+            let equal: Vec<Expr> = sizes.windows(2).map(|pair| pair[0].sub(pair[1])).collect();
+
+            for expr in equal {
+                model.add(expr, Op::Eq, group.strength);
+            }
+        }
+
+        let values = match model.solve() {
+            Some(values) => values,
+            None => return,
+        };
+
+        for (handle, extent, first) in &model.splits {
+            let extent = extent.value(&values);
+
+            if extent <= 0.0 {
+                continue;
+            }
+
+            if let Some(split) = self.find_split_mut(*handle) {
+                let count = split.children.len() - 1;
+                let mut sizes: Vec<f32> = values[*first..*first + count].iter().map(|&size| size as f32).collect();
+                let rest = extent as f32 - sizes.iter().sum::<f32>();
+                sizes.push(rest.max(0.0));
+
+                split.set_weights(&sizes);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod test {
+    use test_util::check_range;
+    use {tabs, view, Layout, Length, Rect, ResizeStrategy, SizeAxis, SizeConstraint, Strength, Track, ViewConstraints, ViewHandle,
+         Workspace};
+
+    fn width(ws: &Workspace, handle: u64) -> f32 {
+        ws.view(ViewHandle(handle)).unwrap().rect.width
+    }
+
+    fn columns() -> Workspace {
+        let layout = Layout::vertical(0.5).left(view(1)).right(Layout::vertical(0.5).left(view(2)).right(view(3)));
+        Workspace::from_layout(Rect::new(0.0, 0.0, 1000.0, 600.0), layout).unwrap()
+    }
+
+    #[test]
+    fn test_min_and_preferred() {
+        let mut ws = columns();
+
+        // Without constraints the ratios stay
+        ws.update();
+        assert_eq!(width(&ws, 1), 500.0);
+
+        // Prefers 30% of the workspace but is at least 400 pixels
+        let width_constraint = SizeConstraint { min: 400.0, preferred: Some(Length::Percent(30.0)), ..SizeConstraint::default() };
+        ws.set_constraints(ViewHandle(1), ViewConstraints { width: width_constraint, ..ViewConstraints::default() }).unwrap();
+        ws.update();
+        assert_eq!(check_range(width(&ws, 1), 400.0, 0.01), true);
+
+        // Twice as wide there is room for the preferred 30%
+        ws.set_rect(Rect::new(0.0, 0.0, 2000.0, 600.0), ResizeStrategy::Proportional).unwrap();
+        assert_eq!(check_range(width(&ws, 1), 600.0, 0.01), true);
+        // The other split keeps its ratio
+        assert_eq!(check_range(width(&ws, 2), 700.0, 0.01), true);
+    }
+
+    #[test]
+    fn test_equal_columns_and_max() {
+        let mut ws = columns();
+
+        ws.add_equal_sizes(SizeAxis::Width, &[ViewHandle(1), ViewHandle(2), ViewHandle(3)], Strength::Strong).unwrap();
+        ws.update();
+
+        for handle in 1..4 {
+            assert_eq!(check_range(width(&ws, handle), 1000.0 / 3.0, 0.01), true);
+        }
+
+        // A required max wins over the equal columns
+        let max = SizeConstraint { max: 100.0, strength: Strength::Required, ..SizeConstraint::default() };
+        ws.set_constraints(ViewHandle(3), ViewConstraints { width: max, ..ViewConstraints::default() }).unwrap();
+        ws.update();
+        assert_eq!(check_range(width(&ws, 3), 100.0, 0.01), true);
+        assert_eq!(check_range(width(&ws, 1), width(&ws, 2), 0.01), true);
+
+        // Required constraints that can't be met leave the ratios alone
+        let min = SizeConstraint { min: 2000.0, strength: Strength::Required, ..SizeConstraint::default() };
+        ws.set_constraints(ViewHandle(1), ViewConstraints { width: min, ..ViewConstraints::default() }).unwrap();
+        ws.update();
+        assert_eq!(check_range(width(&ws, 3), 100.0, 0.01), true);
+        assert_eq!(ws.validate().is_ok(), true);
+    }
+
+    #[test]
+    fn test_grid_views_are_skipped() {
+        let layout = Layout::vertical(0.5).left(view(1)).right(tabs(&[2, 3]));
+        let mut ws: Workspace = Workspace::from_layout(Rect::new(0.0, 0.0, 1000.0, 600.0), layout).unwrap();
+        ws.make_grid(ViewHandle(2), vec![Track::Fraction(1.0), Track::Fraction(1.0)], vec![Track::Fraction(1.0)]).unwrap();
+
+        // The min would only fit the whole container, not a cell
+        let min = SizeConstraint { min: 800.0, strength: Strength::Required, ..SizeConstraint::default() };
+        ws.set_constraints(ViewHandle(2), ViewConstraints { width: min, ..ViewConstraints::default() }).unwrap();
+        ws.update();
+
+        assert_eq!(width(&ws, 1), 500.0);
+        assert_eq!(width(&ws, 2), 250.0);
+    }
+}
@@ -0,0 +1,135 @@
+use {Rect, Split, Style, ViewHandle, Workspace};
+
+impl<T> Split<T> {
+    ///
+    /// Lays out the containers of this split again in the next `Workspace::update`. The mutators of
+    /// the split, its `_mut` accessors and the lookups of `Workspace` mark it on their own together
+    /// with the splits above it. This is only needed after changing `children` or `direction`
+    /// directly, in which case the splits above it have to be marked as well.
+    ///
+    pub fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+
+    /// True if the split or a split below it changed since it was laid out
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Lays out the split again if it or a split below it is dirty or its rect changed, clean splits are skipped
+    pub(crate) fn incremental_update(&mut self, rect: Rect, style: &Style, scale: f32, pixel_exact: bool,
+                                     changed: &mut Vec<ViewHandle>) {
+        if !self.dirty && self.rect == rect {
+            return;
+        }
+
+        let rects = self.child_rects(rect, pixel_exact);
+
+        self.rect = rect;
+
+        // Containers that didn't move only report changes of their own tabs
+        for (child, rect) in self.children.iter_mut().zip(rects) {
+            if let Some(ref mut split) = child.split {
+                split.incremental_update(rect, style, scale, pixel_exact, changed);
+            }
+
+            child.views.rect = rect;
+            child.views.update_header(style, scale, pixel_exact, changed);
+        }
+
+        self.dirty = false;
+    }
+}
+
+impl<T> Workspace<T> {
+    /// Lays out every split again in the next `update`, see `Split::mark_dirty`
+    pub fn mark_dirty(&mut self) {
+        self.layout_key = None;
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod test {
+    use test_util::{nested_workspace, sorted};
+    use {Direction, Rect, ResizeStrategy, ViewHandle};
+
+    #[test]
+    fn test_reports_changed_views() {
+        let mut ws = nested_workspace();
+
+        // `from_layout` has done the first update already
+        assert_eq!(ws.update().is_empty(), true);
+
+        // Moving the inner sizer only touches the views on both sides of it
+        ws.split.as_mut().unwrap().right_mut().as_mut().unwrap().set_ratio(0.25);
+        assert_eq!(sorted(ws.update()), vec![2, 3]);
+        let rect = ws.view(ViewHandle(2)).unwrap().rect;
+        assert_eq!(rect.y + rect.height, 150.0);
+
+        // `set_rect` lays out everything that moved right away
+        ws.set_rect(Rect::new(0.0, 0.0, 1000.0, 800.0), ResizeStrategy::Proportional).unwrap();
+        assert_eq!(ws.update().is_empty(), true);
+        assert_eq!(ws.view(ViewHandle(1)).unwrap().rect.height, 800.0 - ws.style.title_bar_height);
+
+        ws.split_by_view_handle(Direction::Horizontal, ViewHandle(1), ViewHandle(4)).unwrap();
+        assert_eq!(sorted(ws.update()), vec![1, 4]);
+
+        ws.style.title_bar_height += 4.0;
+        assert_eq!(sorted(ws.update()), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_clean_splits_are_skipped() {
+        let mut ws = nested_workspace();
+        ws.update();
+        assert_eq!(ws.split.as_ref().unwrap().is_dirty(), false);
+
+        // A rect written behind the back of the layout stays until the split is marked dirty
+        ws.split.as_mut().unwrap().children[0].views.views[0].rect = Rect::default();
+        assert_eq!(ws.update().is_empty(), true);
+        assert_eq!(ws.view(ViewHandle(1)).unwrap().rect.width, 0.0);
+
+        ws.split.as_mut().unwrap().mark_dirty();
+        assert_eq!(ws.update(), vec![ViewHandle(1)]);
+        assert_eq!(ws.view(ViewHandle(1)).unwrap().rect.width, 500.0);
+
+        ws.split.as_mut().unwrap().right_mut().as_mut().unwrap().right_views_mut().views[0].rect = Rect::default();
+        ws.mark_dirty();
+        assert_eq!(ws.update(), vec![ViewHandle(3)]);
+
+        // New tabs and tab widths lay out the container again
+        ws.add_tab(ViewHandle(1), ViewHandle(5)).unwrap();
+        assert_eq!(sorted(ws.update()), vec![1, 5]);
+        ws.view_mut(ViewHandle(1)).unwrap().tab_width = 200.0;
+        assert_eq!(sorted(ws.update()), vec![1, 5]);
+        assert_eq!(ws.view(ViewHandle(5)).unwrap().tab_rect.x, 200.0);
+    }
+
+    #[test]
+    fn test_dirty_flags_propagate_up() {
+        let mut ws = nested_workspace();
+        ws.split_by_view_handle(Direction::Horizontal, ViewHandle(1), ViewHandle(4)).unwrap();
+        ws.update();
+
+        let left = ws.split.as_ref().unwrap().left().as_ref().unwrap().handle;
+
+        {
+            let root = ws.split.as_mut().unwrap();
+            assert_eq!(root.is_dirty(), false);
+
+            // Behind the back of the layout again, so it shows which splits are visited
+            root.children[1].split.as_mut().unwrap().children[0].views.views[0].rect = Rect::default();
+        }
+
+        // Dragging a sizer marks its split and the splits above it, the other side is skipped
+        ws.drag_sizer(left, (0.0, 50.0)).unwrap();
+        assert_eq!(ws.split.as_ref().unwrap().is_dirty(), true);
+        assert_eq!(ws.split.as_ref().unwrap().right().as_ref().unwrap().is_dirty(), false);
+
+        assert_eq!(sorted(ws.update()), vec![1, 4]);
+        assert_eq!(ws.view(ViewHandle(2)).unwrap().rect.width, 0.0);
+        assert_eq!(ws.split.as_ref().unwrap().is_dirty(), false);
+        assert_eq!(ws.split.as_ref().unwrap().left().as_ref().unwrap().is_dirty(), false);
+    }
+}
@@ -0,0 +1,347 @@
+use std::iter;
+use {Child, Container, Direction, DropTarget, Error, Rect, Result, Split, SplitChild, SplitHandle, View, ViewHandle, Workspace};
+
+/// The document area in the centre of the workspace or one of the dock zones around it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DockArea {
+    Center,
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+impl DockArea {
+    /// All areas, the document area first
+    pub const ALL: [DockArea; 5] = [DockArea::Center, DockArea::Left, DockArea::Right, DockArea::Top, DockArea::Bottom];
+
+    fn zone_index(self) -> Option<usize> {
+        match self {
+            DockArea::Center => None,
+            DockArea::Left => Some(0),
+            DockArea::Right => Some(1),
+            DockArea::Top => Some(2),
+            DockArea::Bottom => Some(3),
+        }
+    }
+
+    /// Direction new views are added in, dock zones at the sides stack their views
+    fn direction(self) -> Direction {
+        match self {
+            DockArea::Left | DockArea::Right => Direction::Horizontal,
+            _ => Direction::Vertical,
+        }
+    }
+}
+
+/// What a view shows. Documents can only be placed in the document area
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ViewKind {
+    Document,
+    Tool,
+}
+
+/// One of the dock zones along the edges of the workspace
+pub struct DockZone<T = ()> {
+    /// Tree of the tool views in the zone, the zone takes no space when it's None
+    pub split: Option<Box<Split<T>>>,
+    /// Width of the left/right zone or height of the top/bottom zone (in logical units)
+    pub size: f32,
+}
+
+impl<T> DockZone<T> {
+    fn new() -> DockZone<T> {
+        DockZone {
+            split: None,
+            size: 250.0,
+        }
+    }
+}
+
+/// Dock zones in the order of `DockArea::zone_index`
+pub(crate) type DockZones<T> = [DockZone<T>; 4];
+
+pub(crate) fn new_zones<T>() -> DockZones<T> {
+    [DockZone::new(), DockZone::new(), DockZone::new(), DockZone::new()]
+}
+
+impl<T> Split<T> {
+    ///
+    /// Collapses the root of a tree if some of its children became empty. A single remaining
+    /// container turns the root into a `Direction::Full` split and no remaining views clears the tree.
+    ///
+    pub(crate) fn collapse_root(root: &mut Child<T>) {
+        let mut split = match root.take() {
+            Some(split) => split,
+            None => return,
+        };
+
+        if !split.children.iter().any(|child| child.is_empty()) {
+            *root = Some(split);
+            return;
+        }
+
+        split.remove_empty_children();
+
+        if split.children.len() > 1 {
+            *root = Some(split);
+            return;
+        }
+
+        *root = match split.children.pop() {
+            Some(SplitChild { split: Some(child), .. }) => Some(child),
+            Some(child) => Some(Box::new(Split::full(split.handle, child.views))),
+            None => None,
+        };
+    }
+}
+
+impl<T> Workspace<T> {
+    /// Returns the dock zone of the area, None for `DockArea::Center`
+    pub fn dock_zone(&self, area: DockArea) -> Option<&DockZone<T>> {
+        area.zone_index().map(|index| &self.zones[index])
+    }
+
+    pub fn dock_zone_mut(&mut self, area: DockArea) -> Option<&mut DockZone<T>> {
+        area.zone_index().map(move |index| &mut self.zones[index])
+    }
+
+    /// Root of the tree of the area
+    pub(crate) fn tree(&self, area: DockArea) -> &Child<T> {
+        match area.zone_index() {
+            Some(index) => &self.zones[index].split,
+            None => &self.split,
+        }
+    }
+
+    pub(crate) fn tree_mut(&mut self, area: DockArea) -> &mut Child<T> {
+        match area.zone_index() {
+            Some(index) => &mut self.zones[index].split,
+            None => &mut self.split,
+        }
+    }
+
+    /// Roots of the document area and all dock zones
+    pub(crate) fn trees(&self) -> impl Iterator<Item = &Split<T>> {
+        iter::once(&self.split)
+            .chain(self.zones.iter().map(|zone| &zone.split))
+            .filter_map(|root| root.as_ref().map(|split| &**split))
+    }
+
+    pub(crate) fn trees_mut(&mut self) -> impl Iterator<Item = &mut Split<T>> {
+        iter::once(&mut self.split)
+            .chain(self.zones.iter_mut().map(|zone| &mut zone.split))
+            .filter_map(|root| root.as_mut().map(|split| &mut **split))
+    }
+
+    /// Roots in the order of `DockArea::ALL`, including the empty ones
+    pub(crate) fn roots_mut(&mut self) -> impl Iterator<Item = &mut Child<T>> {
+        iter::once(&mut self.split).chain(self.zones.iter_mut().map(|zone| &mut zone.split))
+    }
+
+    /// Returns the split with the handle from any of the trees, for example to read its weights
+    pub fn find_split(&self, handle: SplitHandle) -> Option<&Split<T>> {
+        self.trees().find_map(|split| split.find_split(handle))
+    }
+
+    /// Finds the split with the handle in any of the trees
+    pub(crate) fn find_split_mut(&mut self, handle: SplitHandle) -> Option<&mut Split<T>> {
+        self.trees_mut().find(|split| split.has_split(handle)).and_then(|split| split.find_split_mut(handle))
+    }
+
+    /// Returns the area the view is in
+    pub fn area_of(&self, handle: ViewHandle) -> Option<DockArea> {
+        DockArea::ALL.iter().cloned()
+            .find(|&area| self.tree(area).as_ref().is_some_and(|split| split.has_view(handle)))
+    }
+
+    ///
+    /// Rects of all areas in the order of `DockArea::ALL`. The left and right zones span the full
+    /// height and the top and bottom zones sit between them. Empty zones take no space and zones
+    /// shrink if they would leave less than `Style::min_panel_size` for the document area.
+    ///
+    pub(crate) fn area_rects(&self) -> [Rect; 5] {
+        let content = self.content_rect();
+        let min_size = self.metrics().min_panel_size;
+        let size = |area: DockArea| match self.dock_zone(area) {
+            Some(zone) if zone.split.is_some() => zone.size.max(0.0) * self.scale,
+            _ => 0.0,
+        };
+
+        let fit = |first: f32, second: f32, extent: f32| {
+            let available = (extent - min_size).max(0.0);
+
+            if first + second > available {
+                let factor = available / (first + second);
+                (first * factor, second * factor)
+            } else {
+                (first, second)
+            }
+        };
+
+        let (left, right) = fit(size(DockArea::Left), size(DockArea::Right), content.width);
+        let (top, bottom) = fit(size(DockArea::Top), size(DockArea::Bottom), content.height);
+
+        let x = content.x + left;
+        let width = content.width - left - right;
+
+        [
+            Rect::new(x, content.y + top, width, content.height - top - bottom),
+            Rect::new(content.x, content.y, left, content.height),
+            Rect::new(content.x + content.width - right, content.y, right, content.height),
+            Rect::new(x, content.y, width, top),
+            Rect::new(x, content.y + content.height - bottom, width, bottom),
+        ]
+    }
+
+    /// Rect of the area as laid out by `update`
+    pub fn area_rect(&self, area: DockArea) -> Rect {
+        let index = DockArea::ALL.iter().position(|&other| other == area).unwrap_or(0);
+        self.area_rects()[index]
+    }
+
+    /// Checks the built-in placement rules: documents can only be placed in the document area
+    pub(crate) fn check_area(&self, view: &View<T>, area: DockArea) -> Result<()> {
+        if view.kind == ViewKind::Document && area != DockArea::Center {
+            return Err(Error::ConstraintViolation(format!("document {} can only be placed in the document area",
+                                                          view.handle.0)));
+        }
+
+        Ok(())
+    }
+
+    /// Adds the view to the area. A new zone gets the preferred size of the view if it has one
+    pub(crate) fn dock(&mut self, area: DockArea, view: View<T>) -> Result<()> {
+        self.check_rules(&view, DropTarget::Area(area), area.side())?;
+
+        let direction = area.direction();
+        let preferred = view.preferred_size.map(|size| match area {
+            DockArea::Left | DockArea::Right => size.0,
+            _ => size.1,
+        });
+
+        let split_handle = self.next_split_handle();
+
+        let root = self.tree_mut(area);
+
+        match *root {
+            Some(ref mut split) => split.split_left(split_handle, view, direction),
+            None => {
+                let mut container = Container::new();
+                container.views.push(view);
+                *root = Some(Box::new(Split::full(split_handle, container)));
+
+                if let (Some(zone), Some(size)) = (self.dock_zone_mut(area), preferred) {
+                    zone.size = size;
+                }
+            }
+        }
+
+        self.debug_validate();
+
+        Ok(())
+    }
+
+    /// Adds a new view to the area. Views in the dock zones are stacked along the zone
+    pub fn dock_view(&mut self, area: DockArea, handle: ViewHandle) -> Result<()> where T: Default {
+        self.dock_view_with(area, handle, T::default())
+    }
+
+    /// Same as `dock_view` with the user data owned by the new view
+    pub fn dock_view_with(&mut self, area: DockArea, handle: ViewHandle, payload: T) -> Result<()> {
+        self.check_new_view(handle)?;
+        self.dock(area, View::new(handle, payload))
+    }
+
+    /// Moves a view into the area. The view has to be movable and allowed in the area
+    pub fn move_to_dock(&mut self, area: DockArea, handle: ViewHandle) -> Result<()> {
+        self.check_movable(handle)?;
+
+        if let Some(view) = self.view(handle) {
+            self.check_rules(view, DropTarget::Area(area), area.side())?;
+        }
+
+        let view = self.take_view(handle)?;
+        self.dock(area, view)
+    }
+
+    /// Swaps the contents of two dock zones, used when mirroring the layout
+    pub(crate) fn swap_zones(&mut self, first: DockArea, second: DockArea) {
+        if let (Some(first), Some(second)) = (first.zone_index(), second.zone_index()) {
+            self.zones.swap(first, second);
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod test {
+    use test_util::check_range;
+    use {DockArea, Direction, Rect, ResizeStrategy, ViewHandle, ViewKind, Workspace};
+
+    fn workspace() -> Workspace {
+        let mut ws: Workspace = Workspace::new(Rect::new(0.0, 0.0, 1000.0, 800.0)).unwrap();
+        ws.split_top(ViewHandle(1), Direction::Vertical).unwrap();
+        ws.view_mut(ViewHandle(1)).unwrap().kind = ViewKind::Document;
+        ws.dock_view(DockArea::Left, ViewHandle(2)).unwrap();
+        ws.dock_view(DockArea::Left, ViewHandle(3)).unwrap();
+        ws.dock_view(DockArea::Bottom, ViewHandle(4)).unwrap();
+        ws.dock_zone_mut(DockArea::Bottom).unwrap().size = 200.0;
+        ws.update();
+        ws
+    }
+
+    #[test]
+    fn test_dock_zone_layout() {
+        let ws = workspace();
+
+        let center = ws.view(ViewHandle(1)).unwrap().rect;
+        assert_eq!(check_range(center.x, 250.0, 0.01), true);
+        assert_eq!(check_range(center.width, 750.0, 0.01), true);
+
+        let left = ws.area_rect(DockArea::Left);
+        assert_eq!(check_range(left.height, 800.0, 0.01), true);
+
+        let bottom = ws.area_rect(DockArea::Bottom);
+        assert_eq!(check_range(bottom.x, 250.0, 0.01), true);
+        assert_eq!(check_range(bottom.y, 600.0, 0.01), true);
+
+        // The two views in the left zone are stacked
+        let tool = ws.dock_zone(DockArea::Left).unwrap().split.as_ref().unwrap();
+        assert_eq!(tool.direction, Direction::Horizontal);
+        assert_eq!(ws.area_of(ViewHandle(3)), Some(DockArea::Left));
+        assert_eq!(ws.validate().is_ok(), true);
+    }
+
+    #[test]
+    fn test_zones_keep_size() {
+        let mut ws = workspace();
+        ws.set_rect(Rect::new(0.0, 0.0, 2000.0, 1000.0), ResizeStrategy::Proportional).unwrap();
+
+        assert_eq!(check_range(ws.area_rect(DockArea::Left).width, 250.0, 0.01), true);
+        assert_eq!(check_range(ws.area_rect(DockArea::Bottom).height, 200.0, 0.01), true);
+        assert_eq!(check_range(ws.view(ViewHandle(1)).unwrap().rect.width, 1750.0, 0.01), true);
+    }
+
+    #[test]
+    fn test_documents_stay_in_center() {
+        let mut ws = workspace();
+
+        assert_eq!(ws.move_to_dock(DockArea::Right, ViewHandle(1)).is_err(), true);
+        assert_eq!(ws.move_view(Direction::Vertical, ViewHandle(2), ViewHandle(1)).is_err(), true);
+        assert_eq!(ws.area_of(ViewHandle(1)), Some(DockArea::Center));
+
+        // Tools can go anywhere and empty zones disappear
+        ws.move_to_dock(DockArea::Right, ViewHandle(4)).unwrap();
+        ws.update();
+
+        assert_eq!(ws.dock_zone(DockArea::Bottom).unwrap().split.is_none(), true);
+        assert_eq!(check_range(ws.view(ViewHandle(1)).unwrap().rect.height, 800.0 - ws.style.title_bar_height, 0.01), true);
+        assert_eq!(check_range(ws.view(ViewHandle(4)).unwrap().rect.x, 750.0, 0.01), true);
+
+        ws.remove_view(ViewHandle(2)).unwrap();
+        ws.remove_view(ViewHandle(3)).unwrap();
+        assert_eq!(ws.dock_zone(DockArea::Left).unwrap().split.is_none(), true);
+        assert_eq!(ws.validate().is_ok(), true);
+    }
+}
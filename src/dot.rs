@@ -0,0 +1,106 @@
+use std::fmt::Write;
+use {Container, Rect, Split, Workspace};
+
+fn rect_label(rect: Rect) -> String {
+    format!("x: {} y: {} w: {} h: {}", rect.x, rect.y, rect.width, rect.height)
+}
+
+fn write_container<T>(out: &mut String, parent: &str, side: &str, container: &Container<T>) {
+    let name = format!("{}_{}", parent, side);
+    let views = container.views.iter()
+        .map(|view| view.handle.0.to_string())
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    let _ = writeln!(out, "    {} [shape=ellipse, label=\"Container\\n{}\\nviews: [{}]\"];",
+                     name, rect_label(container.rect), views);
+    let _ = writeln!(out, "    {} -> {} [label=\"{}\"];", parent, name, side);
+}
+
+fn write_split<T>(out: &mut String, split: &Split<T>) {
+    let name = format!("split_{}", split.handle.0);
+    let weights = split.children.iter()
+        .map(|child| child.weight.to_string())
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    let _ = writeln!(out, "    {} [label=\"Split {}\\n{:?}\\nweights: [{}]\\n{}\"];",
+                     name, split.handle.0, split.direction, weights, rect_label(split.rect));
+
+    // A child is normally either a split or a container. Both are written out if present so broken
+    // trees show up in the graph instead of being hidden.
+    for (index, child) in split.children.iter().enumerate() {
+        let side = match index {
+            0 => "left".to_owned(),
+            index if index + 1 == split.children.len() => "right".to_owned(),
+            index => index.to_string(),
+        };
+
+        if let Some(ref nested) = child.split {
+            let _ = writeln!(out, "    {} -> split_{} [label=\"{}\"];", name, nested.handle.0, side);
+            write_split(out, nested);
+        }
+
+        if child.split.is_none() || !child.views.views.is_empty() {
+            write_container(out, &name, &side, &child.views);
+        }
+    }
+}
+
+impl<T> Workspace<T> {
+    ///
+    /// Export the split tree as a Graphviz DOT graph. Each split lists its handle, direction,
+    /// weights and computed rect and each container the views it holds. Render with
+    /// `dot -Tpng workspace.dot -o workspace.png`
+    ///
+    pub fn to_dot(&self) -> String {
+        let mut out = String::new();
+
+        out.push_str("digraph workspace {\n");
+        out.push_str("    node [shape=box, fontname=\"monospace\"];\n");
+
+        let _ = writeln!(out, "    workspace [label=\"Workspace\\n{}\"];", rect_label(self.rect));
+
+        for split in self.trees() {
+            let _ = writeln!(out, "    workspace -> split_{};", split.handle.0);
+            write_split(&mut out, split);
+        }
+
+        out.push_str("}\n");
+        out
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod test {
+    use {Direction, Rect, ViewHandle, Workspace};
+
+    #[test]
+    fn test_dot_empty_workspace() {
+        let ws: Workspace = Workspace::new(Rect::new(0.0, 0.0, 1024.0, 1024.0)).unwrap();
+        let dot = ws.to_dot();
+
+        assert_eq!(dot.starts_with("digraph workspace {"), true);
+        assert_eq!(dot.contains("split_"), false);
+    }
+
+    #[test]
+    fn test_dot_lists_splits_and_views() {
+        let mut ws: Workspace = Workspace::new(Rect::new(0.0, 0.0, 1024.0, 1024.0)).unwrap();
+        ws.split_top(ViewHandle(1), Direction::Vertical).unwrap();
+        ws.split_top(ViewHandle(2), Direction::Vertical).unwrap();
+        ws.split_by_view_handle(Direction::Vertical, ViewHandle(1), ViewHandle(3)).unwrap();
+        ws.update();
+
+        let dot = ws.to_dot();
+
+        assert_eq!(dot.contains("split_2 [label=\"Split 2\\nVertical\\nweights: [0.5, 0.5]"), true);
+        assert_eq!(dot.contains("split_2 -> split_4 [label=\"left\"];"), true);
+        assert_eq!(dot.contains("split_4 [label=\"Split 4\\nVertical\\nweights: [0.5, 0.5]\\nx: 0 y: 0 w: 512 h: 1024"), true);
+        assert_eq!(dot.contains("views: [3]"), true);
+        assert_eq!(dot.contains("views: [2]"), true);
+        assert_eq!(dot.contains("views: [1]"), true);
+        assert_eq!(dot.contains("x: 512 y: 0 w: 512 h: 1024"), true);
+    }
+}
@@ -0,0 +1,277 @@
+use std::mem;
+use {Container, Direction, DockArea, DropSide, DropTarget, Error, Rect, Result, Split, View, ViewHandle, Workspace};
+
+/// A place a dragged view can be dropped, see `Workspace::drop_zones`
+#[derive(Debug, Clone, Copy)]
+pub struct DropZone {
+    pub target: DropTarget,
+    pub side: DropSide,
+    /// Area that accepts the drop, from the last `update`
+    pub rect: Rect,
+}
+
+impl<T> Split<T> {
+    fn collect_containers<'a>(&'a self, out: &mut Vec<&'a Container<T>>) {
+        for child in &self.children {
+            match child.split {
+                Some(ref split) => split.collect_containers(out),
+                None if !child.views.views.is_empty() => out.push(&child.views),
+                None => (),
+            }
+        }
+    }
+}
+
+impl<T> Workspace<T> {
+    /// Puts the view on the side of the container holding `target` or into it for `DropSide::Tab`
+    pub(crate) fn place_view(&mut self, view: View<T>, target: ViewHandle, side: DropSide) -> Result<()> {
+        let area = self.area_of(target).ok_or(Error::ViewNotFound(target))?;
+
+        let split_handle = self.next_split_handle();
+
+        let tree = self.tree_mut(area);
+        let root = tree.as_mut().ok_or(Error::ViewNotFound(target))?;
+        let is_full = root.direction == Direction::Full;
+
+        {
+            let child = root.child_with_view_mut(target).ok_or(Error::ViewNotFound(target))?;
+
+            if side == DropSide::Tab {
+                child.views.views.push(view);
+                return Ok(());
+            }
+
+            let direction = match side {
+                DropSide::Left | DropSide::Right => Direction::Vertical,
+                _ => Direction::Horizontal,
+            };
+
+            let (old, new) = if side == DropSide::Left || side == DropSide::Top { (1, 0) } else { (0, 1) };
+
+            let mut split = Box::new(Split::new(direction, split_handle));
+            split.children[old].views = mem::take(&mut child.views);
+            split.children[new].views.views.push(view);
+
+            child.split = Some(split);
+        }
+
+        // A full root holds a single container so the new split replaces it
+        if is_full {
+            *tree = root.left_mut().take();
+        }
+
+        Ok(())
+    }
+
+    /// Checks everything needed to move the view to the side of the target before it's taken out
+    pub(crate) fn check_move(&self, handle: ViewHandle, target: ViewHandle, side: DropSide) -> Result<()> {
+        self.check_movable(handle)?;
+        self.check_dockable(target)?;
+
+        if handle == target {
+            return Err(Error::ConstraintViolation("a view can't be moved next to itself".to_owned()));
+        }
+
+        self.check_rules(self.view(handle).ok_or(Error::ViewNotFound(handle))?, DropTarget::View(target), side)
+    }
+
+    /// Adds a new view as a tab to the container holding `target`
+    pub fn add_tab(&mut self, target: ViewHandle, handle: ViewHandle) -> Result<()> where T: Default {
+        self.add_tab_with(target, handle, T::default())
+    }
+
+    /// Same as `add_tab` with the user data owned by the new view
+    pub fn add_tab_with(&mut self, target: ViewHandle, handle: ViewHandle, payload: T) -> Result<()> {
+        self.check_new_view(handle)?;
+        self.check_dockable(target)?;
+
+        let view = View::new(handle, payload);
+        self.check_rules(&view, DropTarget::View(target), DropSide::Tab)?;
+        self.place_view(view, target, DropSide::Tab)?;
+
+        self.debug_validate();
+
+        Ok(())
+    }
+
+    /// Moves the view into the container holding `target` as a new tab
+    pub fn move_to_tab(&mut self, target: ViewHandle, handle: ViewHandle) -> Result<()> {
+        self.move_to_side(target, handle, DropSide::Tab)
+    }
+
+    /// Moves the view to the side of the container holding `target`, or into it for `DropSide::Tab`
+    pub fn move_to_side(&mut self, target: ViewHandle, handle: ViewHandle, side: DropSide) -> Result<()> {
+        self.check_move(handle, target, side)?;
+
+        let view = self.take_view(handle)?;
+        self.place_view(view, target, side)?;
+
+        self.debug_validate();
+
+        Ok(())
+    }
+
+    ///
+    /// Places where the view can be dropped while it's dragged: strips along the edges of the
+    /// workspace for the dock zones, the document area when it's empty and the left, right, top,
+    /// bottom and tab markers in the middle of every container. Zones the rules don't allow are left out and
+    /// nothing is returned for views that aren't movable. Sizes come from `Style::drop_zone_size`
+    /// and `Style::edge_drop_zone_size`.
+    ///
+    pub fn drop_zones(&self, handle: ViewHandle) -> Vec<DropZone> {
+        let mut zones = Vec::new();
+
+        let view = match self.view(handle) {
+            Some(view) if view.flags.movable => view,
+            _ => return zones,
+        };
+
+        let style = self.metrics();
+        let content = self.content_rect();
+        let edge = style.edge_drop_zone_size.min(content.width * 0.5).min(content.height * 0.5);
+
+        let edges = [
+            (DockArea::Left, Rect::new(content.x, content.y, edge, content.height)),
+            (DockArea::Right, Rect::new(content.x + content.width - edge, content.y, edge, content.height)),
+            (DockArea::Top, Rect::new(content.x, content.y, content.width, edge)),
+            (DockArea::Bottom, Rect::new(content.x, content.y + content.height - edge, content.width, edge)),
+        ];
+
+        for &(area, rect) in &edges {
+            if self.check_rules(view, DropTarget::Area(area), area.side()).is_ok() {
+                zones.push(DropZone { target: DropTarget::Area(area), side: area.side(), rect });
+            }
+        }
+
+        let mut containers = Vec::new();
+
+        for split in self.trees() {
+            split.collect_containers(&mut containers);
+        }
+
+        let size = style.drop_zone_size;
+
+        for container in containers {
+            let target = match container.views.iter().find(|other| other.handle != handle) {
+                Some(other) if other.flags.dockable => DropTarget::View(other.handle),
+                _ => continue,
+            };
+
+            let x = container.rect.x + container.rect.width * 0.5 - size * 0.5;
+            let y = container.rect.y + container.rect.height * 0.5 - size * 0.5;
+
+            let markers = [
+                (DropSide::Tab, Rect::new(x, y, size, size)),
+                (DropSide::Left, Rect::new(x - size, y, size, size)),
+                (DropSide::Right, Rect::new(x + size, y, size, size)),
+                (DropSide::Top, Rect::new(x, y - size, size, size)),
+                (DropSide::Bottom, Rect::new(x, y + size, size, size)),
+            ];
+
+            for &(side, rect) in &markers {
+                if self.check_rules(view, target, side).is_ok() {
+                    zones.push(DropZone { target, side, rect });
+                }
+            }
+        }
+
+        if self.split.is_none() {
+            let target = DropTarget::Area(DockArea::Center);
+
+            if self.check_rules(view, target, DockArea::Center.side()).is_ok() {
+                zones.push(DropZone { target, side: DockArea::Center.side(), rect: self.area_rect(DockArea::Center) });
+            }
+        }
+
+        zones
+    }
+
+    /// Returns the drop zone under the position while the view is dragged, see `drop_zones`
+    pub fn drop_zone_at(&self, handle: ViewHandle, pos: (f32, f32)) -> Option<DropZone> {
+        self.drop_zones(handle).into_iter().find(|zone| Split::is_inside(pos, zone.rect))
+    }
+
+    /// Moves the view to the drop zone. Fails if the rules don't allow it
+    pub fn drop_view(&mut self, handle: ViewHandle, zone: &DropZone) -> Result<()> {
+        match zone.target {
+            DropTarget::Area(area) => self.move_to_dock(area, handle),
+            DropTarget::View(target) => self.move_to_side(target, handle, zone.side),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod test {
+    use test_util::nested_workspace;
+    use {Direction, DockArea, DropSide, DropTarget, View, ViewHandle};
+
+    #[test]
+    fn test_tabs_and_sides() {
+        let mut ws = nested_workspace();
+
+        ws.add_tab(ViewHandle(1), ViewHandle(4)).unwrap();
+        ws.move_to_tab(ViewHandle(1), ViewHandle(2)).unwrap();
+        ws.update();
+
+        let root = ws.split.as_ref().unwrap();
+        assert_eq!(root.left_views().views.len(), 3);
+        assert_eq!(root.direction, Direction::Vertical);
+
+        // Splitting view 4 off below the tabs keeps view 1 and 2 on top
+        ws.move_to_side(ViewHandle(1), ViewHandle(4), DropSide::Bottom).unwrap();
+        ws.update();
+
+        let view = ws.view(ViewHandle(4)).unwrap().rect;
+        assert_eq!(view.x, 0.0);
+        assert_eq!(view.y > 300.0, true);
+        assert_eq!(ws.validate().is_ok(), true);
+    }
+
+    #[test]
+    fn test_drop_zones_follow_rules() {
+        let mut ws = nested_workspace();
+
+        // Views 1 and 2 must never be tabbed together and view 1 only docks to the right edge
+        ws.set_rules(|view: &View, target: DropTarget, side: DropSide| {
+            let pair = (view.handle.0.min(target_view(target)), view.handle.0.max(target_view(target)));
+
+            if side == DropSide::Tab && pair == (1, 2) {
+                return false;
+            }
+
+            match target {
+                DropTarget::Area(area) => view.handle != ViewHandle(1) || area == DockArea::Right,
+                DropTarget::View(_) => true,
+            }
+        });
+
+        let zones = ws.drop_zones(ViewHandle(1));
+        let has = |target: DropTarget, side: DropSide| zones.iter().any(|zone| zone.target == target && zone.side == side);
+
+        assert_eq!(has(DropTarget::View(ViewHandle(2)), DropSide::Tab), false);
+        assert_eq!(has(DropTarget::View(ViewHandle(2)), DropSide::Left), true);
+        assert_eq!(has(DropTarget::View(ViewHandle(3)), DropSide::Tab), true);
+        assert_eq!(has(DropTarget::Area(DockArea::Left), DockArea::Left.side()), false);
+        assert_eq!(has(DropTarget::Area(DockArea::Right), DockArea::Right.side()), true);
+
+        // The marker in the middle of view 3 is the tab zone
+        let zone = ws.drop_zone_at(ViewHandle(1), (750.0, 450.0)).unwrap();
+        assert_eq!(zone.target, DropTarget::View(ViewHandle(3)));
+        assert_eq!(zone.side, DropSide::Tab);
+
+        assert_eq!(ws.move_to_tab(ViewHandle(2), ViewHandle(1)).is_err(), true);
+        assert_eq!(ws.move_to_dock(DockArea::Left, ViewHandle(1)).is_err(), true);
+
+        ws.drop_view(ViewHandle(1), &zone).unwrap();
+        assert_eq!(ws.view(ViewHandle(3)).is_some(), true);
+        assert_eq!(ws.split.as_ref().unwrap().direction, Direction::Horizontal);
+    }
+
+    fn target_view(target: DropTarget) -> u64 {
+        match target {
+            DropTarget::View(handle) => handle.0,
+            DropTarget::Area(_) => 0,
+        }
+    }
+}
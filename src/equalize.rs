@@ -0,0 +1,122 @@
+use {Direction, Error, Result, Split, SplitHandle, Workspace};
+
+/// Number of leaves next to each other along the direction
+fn leaf_count<T>(child: &Option<Box<Split<T>>>, direction: Direction) -> usize {
+    match *child {
+        Some(ref split) => split.leaf_count(direction),
+        None => 1,
+    }
+}
+
+impl<T> Split<T> {
+    fn leaf_count(&self, direction: Direction) -> usize {
+        let counts = self.children.iter().map(|child| leaf_count(&child.split, direction));
+
+        if self.direction == Direction::Full {
+            1
+        } else if self.direction == direction {
+            counts.sum()
+        } else {
+            counts.max().unwrap_or(1)
+        }
+    }
+
+    ///
+    /// Sets the weights of this split and all splits below it so every leaf gets the same amount
+    /// of space along the direction of the split.
+    ///
+    pub fn equalize(&mut self) {
+        if self.direction != Direction::Full {
+            let counts: Vec<f32> = self.children.iter()
+                .map(|child| leaf_count(&child.split, self.direction) as f32)
+                .collect();
+
+            self.set_weights(&counts);
+        }
+
+        for child in &mut self.children {
+            if let Some(ref mut split) = child.split {
+                split.equalize();
+                self.dirty |= split.dirty;
+            }
+        }
+    }
+}
+
+impl<T> Workspace<T> {
+    ///
+    /// Sets all ratios so every leaf along a direction gets the same amount of space. Nested splits
+    /// in the same direction are counted as well so three columns made from two splits end up with
+    /// 1/3 each instead of 1/2, 1/4 and 1/4.
+    ///
+    pub fn equalize(&mut self) {
+        for split in self.trees_mut() {
+            split.equalize();
+        }
+
+        self.debug_validate();
+    }
+
+    /// Equalizes the split with the handle and all splits below it, see `equalize`
+    pub fn equalize_subtree(&mut self, handle: SplitHandle) -> Result<()> {
+        match self.find_split_mut(handle) {
+            Some(split) => split.equalize(),
+            None => return Err(Error::SplitNotFound(handle)),
+        }
+
+        self.debug_validate();
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod test {
+    use test_util::check_range;
+    use {view, Layout, Rect, SplitHandle, Workspace};
+
+    fn workspace() -> Workspace {
+        // A | (B / (C | D)) with lopsided ratios
+        let layout = Layout::vertical(0.8)
+            .left(view(1))
+            .right(Layout::horizontal(0.1)
+                .top(view(2))
+                .bottom(Layout::vertical(0.9).left(view(3)).right(view(4))));
+
+        Workspace::<()>::from_layout(Rect::new(0.0, 0.0, 900.0, 900.0), layout).unwrap()
+    }
+
+    #[test]
+    fn test_equalize_counts_nested_leaves() {
+        let mut ws = workspace();
+        ws.equalize();
+        ws.update();
+
+        let root = ws.split.as_ref().unwrap();
+        let right = root.right().as_ref().unwrap();
+        let bottom = right.right().as_ref().unwrap();
+
+        assert_eq!(check_range(root.left_views().rect.width, 300.0, 0.01), true);
+        assert_eq!(check_range(right.left_views().rect.height, 450.0, 0.01), true);
+        assert_eq!(check_range(bottom.left_views().rect.width, 300.0, 0.01), true);
+        assert_eq!(check_range(bottom.right_views().rect.width, 300.0, 0.01), true);
+    }
+
+    #[test]
+    fn test_equalize_subtree() {
+        let mut ws = workspace();
+        let right = ws.split.as_ref().unwrap().right().as_ref().unwrap().handle;
+
+        ws.equalize_subtree(right).unwrap();
+
+        let root = ws.split.as_ref().unwrap();
+        let right = root.right().as_ref().unwrap();
+
+        assert_eq!(check_range(root.ratio(), 0.8, 0.001), true);
+        assert_eq!(check_range(right.ratio(), 0.5, 0.001), true);
+        assert_eq!(check_range(right.right().as_ref().unwrap().ratio(), 0.5, 0.001), true);
+
+        assert_eq!(ws.equalize_subtree(SplitHandle(1000)).is_err(), true);
+    }
+}
@@ -1,5 +1,6 @@
 use std::error::Error as StdError;
 use std::fmt;
+use {Rect, SplitHandle, ViewHandle, WorkspaceHandle};
 
 /// Errors that can be return from various operatiors
 ///
@@ -7,27 +8,115 @@ use std::fmt;
 pub enum Error {
     /// Size of Workspace is incorrect
     IllegalSize(String),
+    /// No view with the handle exists in the workspace
+    ViewNotFound(ViewHandle),
+    /// No split with the handle exists in the workspace
+    SplitNotFound(SplitHandle),
+    /// A view with the handle already exists in the workspace
+    DuplicateHandle(ViewHandle),
+    /// No workspace with the handle exists in the `WorkspaceManager`
+    WorkspaceNotFound(WorkspaceHandle),
+    /// No layout with the name was saved with `Workspace::save_layout`
+    LayoutNotFound(String),
+    /// The operation would break a layout constraint
+    ConstraintViolation(String),
+    /// Failed to parse a serialized layout
+    ParseError { line: usize, col: usize, message: String },
+    /// The split tree is inconsistent, see `TreeError` for details
+    InvalidTree(TreeError),
+}
+
+/// Inconsistencies found in the split tree by `Workspace::validate`
+///
+#[derive(Debug)]
+pub enum TreeError {
+    /// The same view handle is used more than once in the tree
+    DuplicateViewHandle(ViewHandle),
+    /// The same split handle is used more than once in the tree
+    DuplicateSplitHandle(SplitHandle),
+    /// A leaf container of the split has no views
+    EmptyContainer(SplitHandle),
+    /// One side of the split has both a child split and views
+    AmbiguousSide(SplitHandle),
+    /// Weight of a child is outside of (0.0, 1.0) or the weights of the split don't sum up to 1.0
+    InvalidRatio(SplitHandle, f32),
+    /// A `Direction::Full` split that isn't the root or that holds more than one container
+    MisplacedFullSplit(SplitHandle),
+    /// A leaf rect of the split is negative, not finite or outside of the workspace
+    InvalidRect(SplitHandle, Rect),
+    /// The rects of the containers holding the two views overlap
+    OverlappingRects(ViewHandle, ViewHandle),
+    /// The leaf rects doesn't cover the whole workspace
+    IncompleteTiling { covered: f32, expected: f32 },
+}
+
+impl From<TreeError> for Error {
+    fn from(err: TreeError) -> Error {
+        Error::InvalidTree(err)
+    }
 }
 
 impl StdError for Error {
-    fn description(&self) -> &str {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
         match *self {
-            Error::IllegalSize(_) => "Illegal Size",
+            Error::InvalidTree(ref err) => Some(err),
+            _ => None,
         }
     }
+}
 
-    fn cause(&self) -> Option<&StdError> {
+impl fmt::Display for Error {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         match *self {
-            Error::IllegalSize(_) => None,
+            Error::IllegalSize(ref name) => write!(fmt, "Illegal Size {}", name),
+            Error::ViewNotFound(handle) => write!(fmt, "View {} not found", handle.0),
+            Error::SplitNotFound(handle) => write!(fmt, "Split {} not found", handle.0),
+            Error::DuplicateHandle(handle) => write!(fmt, "View {} already exists", handle.0),
+            Error::WorkspaceNotFound(handle) => write!(fmt, "Workspace {} not found", handle.0),
+            Error::LayoutNotFound(ref name) => write!(fmt, "Layout {} not found", name),
+            Error::ConstraintViolation(ref reason) => write!(fmt, "Constraint violation: {}", reason),
+            Error::ParseError { line, col, ref message } => {
+                write!(fmt, "Parse error at line {} column {}: {}", line, col, message)
+            }
+            Error::InvalidTree(_) => write!(fmt, "Invalid split tree"),
         }
     }
 }
 
-impl fmt::Display for Error {
+impl StdError for TreeError {}
+
+impl fmt::Display for TreeError {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         match *self {
-            Error::IllegalSize(ref name) => write!(fmt, "{} {}", self.description(), name),
+            TreeError::DuplicateViewHandle(handle) => write!(fmt, "Duplicate view handle {}", handle.0),
+            TreeError::DuplicateSplitHandle(handle) => write!(fmt, "Duplicate split handle {}", handle.0),
+            TreeError::EmptyContainer(handle) => write!(fmt, "Split {} has an empty container", handle.0),
+            TreeError::AmbiguousSide(handle) => {
+                write!(fmt, "Split {} has a side with both a child split and views", handle.0)
+            }
+            TreeError::InvalidRatio(handle, ratio) => write!(fmt, "Split {} has invalid weight {}", handle.0, ratio),
+            TreeError::MisplacedFullSplit(handle) => {
+                write!(fmt, "Split {} is Full but isn't the root holding a single container", handle.0)
+            }
+            TreeError::InvalidRect(handle, rect) => write!(fmt, "Split {} has invalid rect {:?}", handle.0, rect),
+            TreeError::OverlappingRects(a, b) => write!(fmt, "Rects of views {} and {} overlap", a.0, b.0),
+            TreeError::IncompleteTiling { covered, expected } => {
+                write!(fmt, "Containers cover an area of {} but workspace is {}", covered, expected)
+            }
         }
     }
 }
 
+#[cfg(test)]
+mod test {
+    use std::error::Error as StdError;
+    use {Error, SplitHandle, TreeError};
+
+    #[test]
+    fn test_invalid_tree_source() {
+        let err = Error::from(TreeError::EmptyContainer(SplitHandle(3)));
+        let source = err.source().expect("InvalidTree should have a source");
+
+        assert_eq!(source.to_string(), "Split 3 has an empty container");
+    }
+}
@@ -0,0 +1,174 @@
+use {Container, Error, Rect, Result, Split, View, ViewHandle, Workspace};
+
+/// A container that isn't docked but floats above the workspace, see `Workspace::float_view`
+pub struct FloatingWindow<T = ()> {
+    pub container: Container<T>,
+    /// Position and size of the window, it may reach outside of `Workspace::rect`
+    pub rect: Rect,
+}
+
+pub(crate) fn check_window_rect(rect: Rect) -> Result<()> {
+    if !(rect.x.is_finite() && rect.y.is_finite() && rect.width.is_finite() && rect.height.is_finite()) {
+        return Err(Error::IllegalSize("the rect of a floating window has to be finite".to_owned()));
+    }
+
+    if rect.width > 0.0 && rect.height > 0.0 {
+        Ok(())
+    } else {
+        Err(Error::IllegalSize("a floating window has to be larger than 0x0".to_owned()))
+    }
+}
+
+impl<T> Workspace<T> {
+    ///
+    /// Takes the view out of its container and puts it into a new floating window with the rect.
+    /// The view has to be movable. It keeps its handle and can be docked again with `move_to_dock`,
+    /// `move_to_side` or `drop_view`. New windows are on top of the older ones.
+    ///
+    pub fn float_view(&mut self, handle: ViewHandle, rect: Rect) -> Result<()> {
+        self.check_movable(handle)?;
+
+        check_window_rect(rect)?;
+
+        let view = self.take_view(handle)?;
+
+        let mut container = Container::new();
+        container.views.push(view);
+        self.floating.push(FloatingWindow { container, rect });
+
+        self.debug_validate();
+
+        Ok(())
+    }
+
+    /// Iterates over the floating windows from the bottom to the top
+    pub fn floating_windows(&self) -> impl Iterator<Item = &FloatingWindow<T>> {
+        self.floating.iter()
+    }
+
+    /// Returns the floating window holding the view
+    pub fn floating_window(&self, handle: ViewHandle) -> Option<&FloatingWindow<T>> {
+        self.floating.iter().find(|window| window.container.views.iter().any(|view| view.handle == handle))
+    }
+
+    pub(crate) fn is_floating(&self, handle: ViewHandle) -> bool {
+        self.floating_window(handle).is_some()
+    }
+
+    /// Moves the floating window holding the view to the rect and brings it to the top
+    pub fn move_floating(&mut self, handle: ViewHandle, rect: Rect) -> Result<()> {
+        check_window_rect(rect)?;
+
+        let index = self.floating.iter()
+            .position(|window| window.container.views.iter().any(|view| view.handle == handle))
+            .ok_or(Error::ViewNotFound(handle))?;
+
+        let mut window = self.floating.remove(index);
+        window.rect = rect;
+        self.floating.push(window);
+
+        Ok(())
+    }
+
+    /// Returns a view of the topmost floating window at the position
+    pub fn floating_at(&self, pos: (f32, f32)) -> Option<ViewHandle> {
+        self.floating.iter().rev()
+            .find(|window| Split::is_inside(pos, window.rect))
+            .and_then(|window| window.container.views.first())
+            .map(|view| view.handle)
+    }
+
+    pub(crate) fn find_floating_view(&self, handle: ViewHandle) -> Option<&View<T>> {
+        self.floating.iter()
+            .flat_map(|window| window.container.views.iter())
+            .find(|view| view.handle == handle)
+    }
+
+    pub(crate) fn find_floating_view_mut(&mut self, handle: ViewHandle) -> Option<&mut View<T>> {
+        self.floating.iter_mut()
+            .flat_map(|window| window.container.views.iter_mut())
+            .find(|view| view.handle == handle)
+    }
+
+    /// Removes the view from its floating window, which is closed when it becomes empty
+    pub(crate) fn take_floating_view(&mut self, handle: ViewHandle) -> Option<View<T>> {
+        for index in 0..self.floating.len() {
+            let views = &mut self.floating[index].container.views;
+
+            if let Some(position) = views.iter().position(|view| view.handle == handle) {
+                let view = views.remove(position);
+
+                if views.is_empty() {
+                    self.floating.remove(index);
+                }
+
+                return Some(view);
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod test {
+    use test_util::workspace;
+    use {Direction, DockArea, Error, Rect, TabHit, ViewHandle};
+
+    #[test]
+    fn test_float_and_dock_again() {
+        let mut ws = workspace();
+
+        ws.float_view(ViewHandle(2), Rect::new(100.0, 100.0, 300.0, 200.0)).unwrap();
+        assert_eq!(ws.update().contains(&ViewHandle(1)), true);
+
+        // The view keeps its handle and is laid out inside the window
+        let view = ws.view(ViewHandle(2)).unwrap();
+        assert_eq!(view.rect.y, 100.0 + ws.style.title_bar_height);
+        assert_eq!(view.rect.width, 300.0);
+        assert_eq!(ws.view(ViewHandle(1)).unwrap().rect.width, 1000.0);
+        assert_eq!(ws.hit_tab((150.0, 105.0)), Some(TabHit::Tab(ViewHandle(2))));
+        assert_eq!(ws.floating_at((150.0, 250.0)), Some(ViewHandle(2)));
+
+        match ws.split_top(ViewHandle(2), Direction::Vertical) {
+            Err(Error::DuplicateHandle(ViewHandle(2))) => (),
+            _ => panic!("expected DuplicateHandle"),
+        }
+
+        ws.move_to_dock(DockArea::Left, ViewHandle(2)).unwrap();
+        assert_eq!(ws.floating_windows().count(), 0);
+        assert_eq!(ws.area_of(ViewHandle(2)), Some(DockArea::Left));
+    }
+
+    #[test]
+    fn test_window_order() {
+        let mut ws = workspace();
+
+        ws.float_view(ViewHandle(1), Rect::new(0.0, 0.0, 200.0, 200.0)).unwrap();
+        ws.float_view(ViewHandle(2), Rect::new(100.0, 100.0, 200.0, 200.0)).unwrap();
+        assert_eq!(ws.split.is_none(), true);
+        assert_eq!(ws.floating_at((150.0, 150.0)), Some(ViewHandle(2)));
+
+        ws.move_floating(ViewHandle(1), Rect::new(50.0, 50.0, 200.0, 200.0)).unwrap();
+        assert_eq!(ws.floating_at((150.0, 150.0)), Some(ViewHandle(1)));
+        assert_eq!(ws.move_floating(ViewHandle(1), Rect::new(0.0, 0.0, 0.0, 10.0)).is_err(), true);
+    }
+
+    #[test]
+    fn test_reject_non_finite_window_rects() {
+        let mut ws = workspace();
+
+        for &rect in &[Rect::new(0.0, 0.0, f32::INFINITY, f32::NAN), Rect::new(f32::NAN, 0.0, 200.0, 200.0),
+                       Rect::new(0.0, f32::NEG_INFINITY, 200.0, 200.0)] {
+            match ws.float_view(ViewHandle(1), rect) {
+                Err(Error::IllegalSize(_)) => (),
+                _ => panic!("expected IllegalSize"),
+            }
+        }
+
+        ws.float_view(ViewHandle(1), Rect::new(0.0, 0.0, 200.0, 200.0)).unwrap();
+        assert_eq!(ws.move_floating(ViewHandle(1), Rect::new(0.0, 0.0, 200.0, f32::INFINITY)).is_err(), true);
+        assert_eq!(ws.floating_window(ViewHandle(1)).unwrap().rect.height, 200.0);
+    }
+}
@@ -0,0 +1,235 @@
+use {DockArea, DropSide, DropTarget, DropZone, Rect, Result, TabHit, ViewHandle, Workspace};
+
+/// Where a dragged view would end up, see `TabDrag::preview`
+#[derive(Debug, Clone, Copy)]
+pub struct DropPreview {
+    pub zone: DropZone,
+    /// The part of the workspace the view would take, from the last `update`
+    pub rect: Rect,
+}
+
+///
+/// A tab that is being dragged, started with `Workspace::begin_tab_drag`. While the pointer stays
+/// within `Style::tear_off_threshold` of where the drag started nothing happens, after that the
+/// view is torn off into a floating window that follows the pointer.
+///
+#[derive(Debug, Clone, Copy)]
+pub struct TabDrag {
+    pub handle: ViewHandle,
+    start: (f32, f32),
+    /// Position of the pointer relative to the top left corner of the tab
+    grab: (f32, f32),
+    /// Size of the floating window
+    size: (f32, f32),
+    torn_off: bool,
+    preview: Option<DropPreview>,
+}
+
+impl TabDrag {
+    /// True once the view is in a floating window
+    pub fn is_torn_off(&self) -> bool {
+        self.torn_off
+    }
+
+    /// The drop zone under the pointer and where the view would go, to be drawn while dragging
+    pub fn preview(&self) -> Option<DropPreview> {
+        self.preview
+    }
+
+    fn window_rect(&self, pos: (f32, f32)) -> Rect {
+        Rect::new(pos.0 - self.grab.0, pos.1 - self.grab.1, self.size.0, self.size.1)
+    }
+}
+
+/// Part of the rect on the side, the whole rect for `DropSide::Tab`
+fn side_rect(rect: Rect, side: DropSide) -> Rect {
+    let half = (rect.width * 0.5, rect.height * 0.5);
+
+    match side {
+        DropSide::Left => Rect::new(rect.x, rect.y, half.0, rect.height),
+        DropSide::Right => Rect::new(rect.x + half.0, rect.y, half.0, rect.height),
+        DropSide::Top => Rect::new(rect.x, rect.y, rect.width, half.1),
+        DropSide::Bottom => Rect::new(rect.x, rect.y + half.1, rect.width, half.1),
+        DropSide::Tab => rect,
+    }
+}
+
+impl<T> Workspace<T> {
+    ///
+    /// Starts dragging the tab at the position. Returns None if there's no tab or the view isn't
+    /// movable. Dragging the tab of a floating window moves the window right away.
+    ///
+    pub fn begin_tab_drag(&self, pos: (f32, f32)) -> Option<TabDrag> {
+        let handle = match self.hit_tab(pos) {
+            Some(TabHit::Tab(handle)) => handle,
+            _ => return None,
+        };
+
+        let view = self.view(handle).filter(|view| view.flags.movable)?;
+        let container = match self.floating_window(handle) {
+            Some(window) => window.rect,
+            None => self.trees().filter_map(|split| split.find_container(handle)).next()?.rect,
+        };
+
+        Some(TabDrag {
+            handle,
+            start: pos,
+            grab: (pos.0 - view.tab_rect.x, pos.1 - view.tab_rect.y),
+            size: (container.width, container.height),
+            torn_off: self.is_floating(handle),
+            preview: None,
+        })
+    }
+
+    ///
+    /// Follows the pointer while a tab is dragged. Once the pointer is further away than
+    /// `Style::tear_off_threshold` the view is torn off into a floating window under the pointer,
+    /// which then moves along. The preview is updated with the drop zone under the pointer, using
+    /// the rects from the last `update`.
+    ///
+    pub fn drag_tab(&mut self, drag: &mut TabDrag, pos: (f32, f32)) -> Result<()> {
+        let rect = drag.window_rect(pos);
+
+        if drag.torn_off {
+            self.move_floating(drag.handle, rect)?;
+        } else {
+            let distance = (pos.0 - drag.start.0).hypot(pos.1 - drag.start.1);
+
+            if distance < self.metrics().tear_off_threshold {
+                return Ok(());
+            }
+
+            self.float_view(drag.handle, rect)?;
+            drag.torn_off = true;
+        }
+
+        drag.preview = self.drop_zone_at(drag.handle, pos)
+            .map(|zone| DropPreview { zone, rect: self.preview_rect(&zone) });
+
+        Ok(())
+    }
+
+    ///
+    /// Ends the drag. If the pointer is over a drop zone the view is docked there with the same
+    /// handle and true is returned, otherwise a torn off view stays in its floating window.
+    ///
+    pub fn end_tab_drag(&mut self, drag: TabDrag, pos: (f32, f32)) -> Result<bool> {
+        if !drag.torn_off {
+            return Ok(false);
+        }
+
+        match self.drop_zone_at(drag.handle, pos) {
+            Some(zone) => {
+                self.drop_view(drag.handle, &zone)?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Rect a view dropped into the zone would roughly take
+    fn preview_rect(&self, zone: &DropZone) -> Rect {
+        let area = match zone.target {
+            DropTarget::View(target) => {
+                return match self.trees().filter_map(|split| split.find_container(target)).next() {
+                    Some(container) => side_rect(container.rect, zone.side),
+                    None => zone.rect,
+                };
+            }
+            DropTarget::Area(area) => area,
+        };
+
+        // A zone that isn't empty gets the view stacked into it, an empty one opens at the edge
+        if area == DockArea::Center || self.tree(area).is_some() {
+            return self.area_rect(area);
+        }
+
+        let content = self.content_rect();
+        let center = self.area_rect(DockArea::Center);
+        let size = self.dock_zone(area).map_or(0.0, |zone| zone.size * self.scale());
+        let width = size.min(content.width * 0.5);
+        let height = size.min(content.height * 0.5);
+
+        match area {
+            DockArea::Left => Rect::new(content.x, content.y, width, content.height),
+            DockArea::Right => Rect::new(content.x + content.width - width, content.y, width, content.height),
+            DockArea::Top => Rect::new(center.x, content.y, center.width, height),
+            _ => Rect::new(center.x, content.y + content.height - height, center.width, height),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod test {
+    use test_util::workspace;
+    use {DockArea, DropSide, DropTarget, ViewHandle};
+
+    #[test]
+    fn test_tear_off_and_drop() {
+        let mut ws = workspace();
+
+        let mut drag = ws.begin_tab_drag((600.0, 10.0)).unwrap();
+        assert_eq!(drag.handle, ViewHandle(2));
+
+        // Below the threshold the tab stays where it is
+        ws.drag_tab(&mut drag, (603.0, 12.0)).unwrap();
+        assert_eq!(drag.is_torn_off(), false);
+        assert_eq!(ws.area_of(ViewHandle(2)), Some(DockArea::Center));
+
+        ws.drag_tab(&mut drag, (400.0, 200.0)).unwrap();
+        assert_eq!(drag.is_torn_off(), true);
+        ws.update();
+
+        let window = ws.floating_window(ViewHandle(2)).unwrap().rect;
+        assert_eq!((window.x, window.y, window.width, window.height), (300.0, 190.0, 500.0, 600.0));
+
+        // Hovering the middle of view 1 shows its tab marker and the whole container as preview
+        ws.drag_tab(&mut drag, (500.0, 300.0)).unwrap();
+        let preview = drag.preview().unwrap();
+        assert_eq!(preview.zone.target, DropTarget::View(ViewHandle(1)));
+        assert_eq!(preview.zone.side, DropSide::Tab);
+        assert_eq!(preview.rect.width, 1000.0);
+
+        // The left marker previews the left half
+        ws.drag_tab(&mut drag, (500.0 - 64.0, 300.0)).unwrap();
+        assert_eq!(drag.preview().unwrap().zone.side, DropSide::Left);
+        assert_eq!(drag.preview().unwrap().rect.width, 500.0);
+
+        assert_eq!(ws.end_tab_drag(drag, (500.0 - 64.0, 300.0)).unwrap(), true);
+        assert_eq!(ws.floating_windows().count(), 0);
+        ws.update();
+
+        assert_eq!(ws.view(ViewHandle(2)).unwrap().rect.x, 0.0);
+        assert_eq!(ws.view(ViewHandle(1)).unwrap().rect.x > 0.0, true);
+    }
+
+    #[test]
+    fn test_drop_outside_keeps_floating() {
+        let mut ws = workspace();
+
+        let mut drag = ws.begin_tab_drag((100.0, 10.0)).unwrap();
+        ws.drag_tab(&mut drag, (300.0, 300.0)).unwrap();
+        ws.update();
+
+        // Far from any marker or edge the view stays in its window
+        ws.drag_tab(&mut drag, (500.0, 150.0)).unwrap();
+        assert_eq!(drag.preview().is_none(), true);
+        assert_eq!(ws.end_tab_drag(drag, (500.0, 150.0)).unwrap(), false);
+        assert_eq!(ws.floating_window(ViewHandle(1)).is_some(), true);
+
+        // Dragging the floating window by its tab docks it to the right edge
+        ws.update();
+        let tab = ws.view(ViewHandle(1)).unwrap().tab_rect;
+        let mut drag = ws.begin_tab_drag((tab.x + 5.0, tab.y + 5.0)).unwrap();
+        assert_eq!(drag.is_torn_off(), true);
+
+        ws.drag_tab(&mut drag, (990.0, 300.0)).unwrap();
+        let preview = drag.preview().unwrap();
+        assert_eq!(preview.zone.target, DropTarget::Area(DockArea::Right));
+        assert_eq!(preview.rect.x, 750.0);
+
+        assert_eq!(ws.end_tab_drag(drag, (990.0, 300.0)).unwrap(), true);
+        assert_eq!(ws.area_of(ViewHandle(1)), Some(DockArea::Right));
+    }
+}
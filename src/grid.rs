@@ -0,0 +1,569 @@
+use header::close_rect;
+use {Container, Error, Rect, Result, Split, Style, View, ViewHandle, Workspace};
+
+/// Size of a column or row of a `Grid`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Track {
+    /// Size in logical units
+    Fixed(f32),
+    /// Share of the space left over by the fixed and auto tracks
+    Fraction(f32),
+    /// Preferred size of the views that sit in this track only, see `View::preferred_size`. Without
+    /// one the track shares the space left over like `Fraction(1.0)`
+    Auto,
+}
+
+/// Place of a view in a `Grid`, it covers `column_span` columns and `row_span` rows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GridCell {
+    pub view: ViewHandle,
+    pub column: usize,
+    pub row: usize,
+    pub column_span: usize,
+    pub row_span: usize,
+}
+
+///
+/// Columns and rows that line up across the whole container. Every view of the container sits in
+/// a cell, views without one are put into the first free cell by `update` and get a new row if the
+/// grid is full. Cells reaching outside of the grid are cut off at its edges.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grid {
+    pub columns: Vec<Track>,
+    pub rows: Vec<Track>,
+    pub cells: Vec<GridCell>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GridAxis {
+    Columns,
+    Rows,
+}
+
+/// The line between track `index` and `index + 1` of a grid, see `Workspace::hovering_grid_line`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GridLine {
+    /// Any view of the grid
+    pub view: ViewHandle,
+    pub axis: GridAxis,
+    pub index: usize,
+}
+
+/// Checks that there's at least one column and row and that all sizes are valid
+pub(crate) fn check_tracks(columns: &[Track], rows: &[Track]) -> Result<()> {
+    let invalid = columns.iter().chain(rows.iter()).any(|track| match *track {
+        Track::Fixed(size) => !(size >= 0.0 && size.is_finite()),
+        Track::Fraction(fraction) => !(fraction > 0.0 && fraction.is_finite()),
+        Track::Auto => false,
+    });
+
+    if columns.is_empty() || rows.is_empty() || invalid {
+        return Err(Error::IllegalSize("a grid needs at least one column and row of valid size".to_owned()));
+    }
+
+    Ok(())
+}
+
+fn not_in_grid(handle: ViewHandle) -> Error {
+    Error::ConstraintViolation(format!("view {} is not in a grid", handle.0))
+}
+
+impl Grid {
+    pub fn new(columns: Vec<Track>, rows: Vec<Track>) -> Grid {
+        Grid { columns, rows, cells: Vec::new() }
+    }
+
+    pub fn cell(&self, view: ViewHandle) -> Option<&GridCell> {
+        self.cells.iter().find(|cell| cell.view == view)
+    }
+
+    fn tracks(&self, axis: GridAxis) -> &[Track] {
+        match axis {
+            GridAxis::Columns => &self.columns,
+            GridAxis::Rows => &self.rows,
+        }
+    }
+
+    fn tracks_mut(&mut self, axis: GridAxis) -> &mut Vec<Track> {
+        match axis {
+            GridAxis::Columns => &mut self.columns,
+            GridAxis::Rows => &mut self.rows,
+        }
+    }
+
+    /// The cell cut off at the edges of the grid, as (column, row, end column, end row)
+    fn clamped(&self, cell: &GridCell) -> (usize, usize, usize, usize) {
+        let column = cell.column.min(self.columns.len() - 1);
+        let row = cell.row.min(self.rows.len() - 1);
+
+        (column, row, column.saturating_add(cell.column_span.max(1)).min(self.columns.len()),
+         row.saturating_add(cell.row_span.max(1)).min(self.rows.len()))
+    }
+
+    fn is_free(&self, column: usize, row: usize, column_span: usize, row_span: usize, ignore: ViewHandle) -> bool {
+        self.cells.iter().filter(|cell| cell.view != ignore).all(|cell| {
+            let (x0, y0, x1, y1) = self.clamped(cell);
+            column.saturating_add(column_span) <= x0 || column >= x1 || row.saturating_add(row_span) <= y0 || row >= y1
+        })
+    }
+
+    /// Reverses the columns or rows, the cells move along so every view keeps its tracks
+    pub(crate) fn mirror(&mut self, axis: GridAxis) {
+        if self.columns.is_empty() || self.rows.is_empty() {
+            return;
+        }
+
+        let count = self.tracks(axis).len();
+
+        for index in 0..self.cells.len() {
+            let (column, row, end_column, end_row) = self.clamped(&self.cells[index]);
+            let cell = &mut self.cells[index];
+
+            match axis {
+                GridAxis::Columns => {
+                    cell.column = count - end_column;
+                    cell.column_span = end_column - column;
+                }
+                GridAxis::Rows => {
+                    cell.row = count - end_row;
+                    cell.row_span = end_row - row;
+                }
+            }
+        }
+
+        self.tracks_mut(axis).reverse();
+    }
+
+    /// Checks that the cell is inside the grid and doesn't overlap the cells of other views
+    pub(crate) fn check_cell(&self, cell: &GridCell) -> Result<()> {
+        let inside = |start: usize, span: usize, count: usize| {
+            span > 0 && start.checked_add(span).is_some_and(|end| end <= count)
+        };
+
+        if !inside(cell.column, cell.column_span, self.columns.len()) || !inside(cell.row, cell.row_span, self.rows.len()) {
+            return Err(Error::ConstraintViolation(format!("cell of view {} is outside of the grid", cell.view.0)));
+        }
+
+        if !self.is_free(cell.column, cell.row, cell.column_span, cell.row_span, cell.view) {
+            return Err(Error::ConstraintViolation(format!("cell of view {} overlaps another view", cell.view.0)));
+        }
+
+        Ok(())
+    }
+
+    /// Drops the cells of views that are gone and gives the views without a cell the first free one
+    pub(crate) fn place_views<T>(&mut self, views: &[View<T>]) {
+        self.cells.retain(|cell| views.iter().any(|view| view.handle == cell.view));
+
+        for view in views {
+            if self.cell(view.handle).is_some() {
+                continue;
+            }
+
+            let free = (0..self.rows.len())
+                .flat_map(|row| (0..self.columns.len()).map(move |column| (column, row)))
+                .find(|&(column, row)| self.is_free(column, row, 1, 1, view.handle));
+
+            let (column, row) = free.unwrap_or_else(|| {
+                self.rows.push(Track::Fraction(1.0));
+                (0, self.rows.len() - 1)
+            });
+
+            self.cells.push(GridCell { view: view.handle, column, row, column_span: 1, row_span: 1 });
+        }
+    }
+
+    /// Size in pixels of every track along the axis when the grid is `extent` pixels large
+    fn track_sizes<T>(&self, axis: GridAxis, extent: f32, style: &Style, scale: f32, views: &[View<T>]) -> Vec<f32> {
+        let tracks = self.tracks(axis);
+        let mut sizes = vec![0.0; tracks.len()];
+        let mut fractions = vec![0.0; tracks.len()];
+
+        for (index, track) in tracks.iter().enumerate() {
+            match *track {
+                Track::Fixed(size) => sizes[index] = size.max(0.0) * scale,
+                Track::Fraction(fraction) => fractions[index] = fraction.max(0.0),
+                Track::Auto => match self.auto_size(axis, index, style, views) {
+                    Some(size) => sizes[index] = size * scale,
+                    None => fractions[index] = 1.0,
+                },
+            }
+        }
+
+        let used: f32 = sizes.iter().sum();
+        let total: f32 = fractions.iter().sum();
+
+        if used > extent {
+            sizes.iter_mut().for_each(|size| *size *= extent / used);
+        } else if total > 0.0 {
+            let left = extent - used;
+            sizes.iter_mut().zip(fractions.iter()).for_each(|(size, fraction)| *size += left * fraction / total);
+        }
+
+        sizes
+    }
+
+    /// Largest preferred size (in logical units) of the views that only cover the track
+    fn auto_size<T>(&self, axis: GridAxis, index: usize, style: &Style, views: &[View<T>]) -> Option<f32> {
+        self.cells.iter()
+            .filter(|cell| match axis {
+                GridAxis::Columns => cell.column == index && cell.column_span <= 1,
+                GridAxis::Rows => cell.row == index && cell.row_span <= 1,
+            })
+            .filter_map(|cell| views.iter().find(|view| view.handle == cell.view))
+            .filter_map(|view| view.preferred_size)
+            .map(|size| match axis {
+                GridAxis::Columns => size.0,
+                // The title bar comes on top of the view
+                GridAxis::Rows => size.1 + style.title_bar_height,
+            })
+            .fold(None, |max: Option<f32>, size| Some(max.map_or(size, |max| max.max(size))))
+    }
+
+    /// Positions of the edges of all tracks, starting at `start`
+    fn edges<T>(&self, axis: GridAxis, start: f32, extent: f32, style: &Style, scale: f32, views: &[View<T>])
+                -> Vec<f32> {
+        let mut edges = vec![start];
+
+        for size in self.track_sizes(axis, extent, style, scale, views) {
+            let edge = edges[edges.len() - 1] + size;
+            edges.push(edge);
+        }
+
+        edges
+    }
+}
+
+impl<T> Container<T> {
+    ///
+    /// Lays out the views in the cells of the grid. Each cell gets a title bar at the top like a
+    /// container with a single view, the tab rect of the view is that title bar.
+    ///
+    pub(crate) fn update_grid(&mut self, style: &Style, scale: f32, pixel_exact: bool, changed: &mut Vec<ViewHandle>) {
+        let rect = self.rect;
+        self.header = Rect::new(rect.x, rect.y, rect.width, 0.0);
+        self.content = rect;
+
+        let grid = match self.grid {
+            Some(ref mut grid) if !grid.columns.is_empty() && !grid.rows.is_empty() => grid,
+            _ => return,
+        };
+
+        grid.place_views(&self.views);
+
+        let mut xs = grid.edges(GridAxis::Columns, rect.x, rect.width, style, scale, &self.views);
+        let mut ys = grid.edges(GridAxis::Rows, rect.y, rect.height, style, scale, &self.views);
+
+        if pixel_exact {
+            xs.iter_mut().chain(ys.iter_mut()).for_each(|edge| *edge = edge.round());
+        }
+
+        for view in &mut self.views {
+            let (column, row, end_column, end_row) = match grid.cell(view.handle) {
+                Some(cell) => grid.clamped(cell),
+                None => continue,
+            };
+
+            let cell = Rect::new(xs[column], ys[row], xs[end_column] - xs[column], ys[end_row] - ys[row]);
+            let mut height = style.title_bar_height.min(cell.height);
+
+            if pixel_exact {
+                height = height.floor();
+            }
+
+            let tab_rect = Rect::new(cell.x, cell.y, cell.width, height);
+            let close_rect = close_rect(tab_rect, view.flags.closable);
+            let content = Rect::new(cell.x, cell.y + height, cell.width, cell.height - height);
+
+            if view.rect != content || view.tab_rect != tab_rect || view.close_rect != close_rect {
+                changed.push(view.handle);
+            }
+
+            view.tab_rect = tab_rect;
+            view.close_rect = close_rect;
+            view.rect = content;
+        }
+    }
+
+    /// The grid line at the position, using the rects from the last `update`
+    fn hovering_grid_line(&self, pos: (f32, f32), style: &Style, scale: f32) -> Option<GridLine> {
+        let grid = self.grid.as_ref()?;
+        let view = self.views.first()?.handle;
+        let rect = self.rect;
+
+        if !Split::is_inside(pos, rect) || grid.columns.is_empty() || grid.rows.is_empty() {
+            return None;
+        }
+
+        let padding = style.sizer_hit_padding;
+
+        for &(axis, start, extent, value) in &[(GridAxis::Columns, rect.x, rect.width, pos.0),
+                                               (GridAxis::Rows, rect.y, rect.height, pos.1)] {
+            let edges = grid.edges(axis, start, extent, style, scale, &self.views);
+
+            // Only the lines between two tracks can be dragged
+            for (index, &edge) in edges[1..edges.len() - 1].iter().enumerate() {
+                if value >= edge - padding && value < edge + style.sizer_size + padding {
+                    return Some(GridLine { view, axis, index });
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl<T> Split<T> {
+    fn collect_grid_containers<'a>(&'a self, out: &mut Vec<&'a Container<T>>) {
+        for child in &self.children {
+            match child.split {
+                Some(ref split) => split.collect_grid_containers(out),
+                None if child.views.grid.is_some() => out.push(&child.views),
+                None => (),
+            }
+        }
+    }
+}
+
+impl<T> Workspace<T> {
+    fn container_mut(&mut self, handle: ViewHandle) -> Result<&mut Container<T>> {
+        let area = self.area_of(handle).ok_or(Error::ViewNotFound(handle))?;
+
+        match *self.tree_mut(area) {
+            Some(ref mut split) => split.child_with_view_mut(handle)
+                .map(|child| &mut child.views)
+                .ok_or(Error::ViewNotFound(handle)),
+            None => Err(Error::ViewNotFound(handle)),
+        }
+    }
+
+    ///
+    /// Turns the container holding the view into a grid with the tracks. The views of the container
+    /// fill the cells row by row, use `set_grid_cell` to move them or let them span cells. Views
+    /// docked into the container later take the first free cell.
+    ///
+    pub fn make_grid(&mut self, handle: ViewHandle, columns: Vec<Track>, rows: Vec<Track>) -> Result<()> {
+        check_tracks(&columns, &rows)?;
+
+        let container = self.container_mut(handle)?;
+        let mut grid = Grid::new(columns, rows);
+        grid.place_views(&container.views);
+        container.grid = Some(grid);
+
+        Ok(())
+    }
+
+    /// Lays out the container holding the view as tabs again
+    pub fn remove_grid(&mut self, handle: ViewHandle) -> Result<Grid> {
+        self.container_mut(handle)?.grid.take().ok_or_else(|| not_in_grid(handle))
+    }
+
+    /// Returns the grid of the container holding the view
+    pub fn grid(&self, handle: ViewHandle) -> Option<&Grid> {
+        self.trees().filter_map(|split| split.find_container(handle)).next().and_then(|container| container.grid.as_ref())
+    }
+
+    /// Returns the grid of the container holding the view to change its tracks
+    pub fn grid_mut(&mut self, handle: ViewHandle) -> Option<&mut Grid> {
+        self.container_mut(handle).ok().and_then(|container| container.grid.as_mut())
+    }
+
+    /// Moves the view to a cell of its grid. The cell has to be inside the grid and free
+    pub fn set_grid_cell(&mut self, handle: ViewHandle, column: usize, row: usize, column_span: usize,
+                         row_span: usize) -> Result<()> {
+        let grid = self.container_mut(handle)?.grid.as_mut().ok_or_else(|| not_in_grid(handle))?;
+        let cell = GridCell { view: handle, column, row, column_span, row_span };
+
+        grid.check_cell(&cell)?;
+
+        grid.cells.retain(|cell| cell.view != handle);
+        grid.cells.push(cell);
+
+        Ok(())
+    }
+
+    /// Returns the grid line at the position, using the rects from the last `update`
+    pub fn hovering_grid_line(&self, pos: (f32, f32)) -> Option<GridLine> {
+        let style = self.metrics();
+        let mut containers = Vec::new();
+
+        for split in self.trees() {
+            split.collect_grid_containers(&mut containers);
+        }
+
+        containers.iter().filter_map(|container| container.hovering_grid_line(pos, &style, self.scale())).next()
+    }
+
+    ///
+    /// Moves the grid line by the delta (in pixels) along its axis, which resizes the two tracks
+    /// next to it in every row or column of the grid. Two fractional tracks share their fraction
+    /// in the new proportion. Next to another kind of track a fractional track has its fraction
+    /// scaled with its size and the other track becomes fixed at its new size, so the other lines
+    /// stay where they are. Both tracks stay at least `Style::min_panel_size` large if there is
+    /// room for it.
+    ///
+    pub fn drag_grid_line(&mut self, line: GridLine, delta: (f32, f32)) -> Result<()> {
+        let style = self.metrics();
+        let scale = self.scale();
+        let container = self.container_mut(line.view)?;
+
+        let (extent, delta) = match line.axis {
+            GridAxis::Columns => (container.rect.width, delta.0),
+            GridAxis::Rows => (container.rect.height, delta.1),
+        };
+
+        let grid = match container.grid {
+            Some(ref mut grid) => grid,
+            None => return Err(not_in_grid(line.view)),
+        };
+
+        let (first, second) = (line.index, line.index + 1);
+
+        if second >= grid.tracks(line.axis).len() {
+            return Err(Error::ConstraintViolation(format!("grid line {} doesn't exist", line.index)));
+        }
+
+        let sizes = grid.track_sizes(line.axis, extent, &style, scale, &container.views);
+        let total = sizes[first] + sizes[second];
+        let min_size = style.min_panel_size.min(total * 0.5);
+        let size = (sizes[first] + delta).clamp(min_size, total - min_size);
+        let new_sizes = [size, total - size];
+
+        let tracks = grid.tracks_mut(line.axis);
+
+        if let (Track::Fraction(a), Track::Fraction(b)) = (tracks[first], tracks[second]) {
+            if total > 0.0 {
+                tracks[first] = Track::Fraction((a + b) * new_sizes[0] / total);
+                tracks[second] = Track::Fraction((a + b) * new_sizes[1] / total);
+            }
+
+            return Ok(());
+        }
+
+        for (index, size) in [(first, new_sizes[0]), (second, new_sizes[1])] {
+            tracks[index] = match tracks[index] {
+                // Fractions map to sizes in the same proportion, so the other fractional tracks keep theirs
+                Track::Fraction(fraction) if sizes[index] > 0.0 => Track::Fraction(fraction * size / sizes[index]),
+                Track::Fraction(fraction) => Track::Fraction(fraction),
+                _ => Track::Fixed(size / scale),
+            };
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod test {
+    use test_util::check_range;
+    use {tabs, GridAxis, Rect, Track, ViewHandle, Workspace};
+
+    fn dashboard() -> Workspace {
+        let mut ws: Workspace = Workspace::from_layout(Rect::new(0.0, 0.0, 1000.0, 600.0), tabs(&[1, 2, 3, 4])).unwrap();
+
+        ws.make_grid(ViewHandle(1), vec![Track::Fixed(200.0), Track::Fraction(1.0), Track::Fraction(1.0)],
+                     vec![Track::Fraction(1.0), Track::Fraction(2.0)]).unwrap();
+        ws.update();
+        ws
+    }
+
+    #[test]
+    fn test_tracks_and_spans() {
+        let mut ws = dashboard();
+
+        // Views fill the cells row by row
+        let rect = |ws: &Workspace, handle: u64| ws.view(ViewHandle(handle)).unwrap().tab_rect;
+        assert_eq!((rect(&ws, 1).x, rect(&ws, 1).width), (0.0, 200.0));
+        assert_eq!((rect(&ws, 2).x, rect(&ws, 2).width), (200.0, 400.0));
+        assert_eq!((rect(&ws, 4).x, rect(&ws, 4).y), (0.0, 200.0));
+
+        // View 4 spans the two fractional columns at the bottom, view 3 moves to the free cell
+        assert_eq!(ws.set_grid_cell(ViewHandle(4), 0, 0, 1, 1).is_err(), true);
+        ws.set_grid_cell(ViewHandle(4), 1, 1, 2, 1).unwrap();
+        ws.set_grid_cell(ViewHandle(3), 0, 1, 1, 1).unwrap();
+        ws.update();
+
+        assert_eq!((rect(&ws, 4).x, rect(&ws, 4).width), (200.0, 800.0));
+        assert_eq!(ws.view(ViewHandle(4)).unwrap().rect.height, 400.0 - ws.style.title_bar_height);
+        assert_eq!(ws.set_grid_cell(ViewHandle(2), 1, 1, 1, 1).is_err(), true);
+
+        // A new tab takes the free cell and a full grid grows a row
+        ws.add_tab(ViewHandle(1), ViewHandle(5)).unwrap();
+        ws.add_tab(ViewHandle(1), ViewHandle(6)).unwrap();
+        ws.update();
+
+        let grid = ws.grid(ViewHandle(6)).unwrap();
+        assert_eq!(grid.cell(ViewHandle(5)).unwrap().column, 2);
+        assert_eq!(grid.cell(ViewHandle(6)).unwrap().row, 2);
+        assert_eq!(grid.rows.len(), 3);
+    }
+
+    #[test]
+    fn test_drag_grid_line() {
+        let mut ws = dashboard();
+
+        // The line between the fixed and the first fractional column
+        let line = ws.hovering_grid_line((201.0, 500.0)).unwrap();
+        assert_eq!((line.axis, line.index), (GridAxis::Columns, 0));
+
+        ws.drag_grid_line(line, (100.0, 0.0)).unwrap();
+        ws.update();
+
+        // The whole column moves in both rows, the line between the fractional columns stays
+        assert_eq!(ws.grid(ViewHandle(1)).unwrap().columns[0], Track::Fixed(300.0));
+        assert_eq!(ws.view(ViewHandle(1)).unwrap().rect.width, 300.0);
+        assert_eq!(ws.view(ViewHandle(4)).unwrap().rect.width, 300.0);
+        assert_eq!(ws.view(ViewHandle(2)).unwrap().rect.x, 300.0);
+        assert_eq!(check_range(ws.view(ViewHandle(2)).unwrap().rect.width, 300.0, 0.01), true);
+        assert_eq!(check_range(ws.view(ViewHandle(3)).unwrap().rect.x, 600.0, 0.01), true);
+        assert_eq!(check_range(ws.view(ViewHandle(3)).unwrap().rect.width, 400.0, 0.01), true);
+
+        // Between two fractional columns the fractions are shared out again
+        let line = ws.hovering_grid_line((601.0, 10.0)).unwrap();
+        assert_eq!(line.index, 1);
+        ws.drag_grid_line(line, (-50.0, 0.0)).unwrap();
+        ws.update();
+
+        assert_eq!(ws.view(ViewHandle(2)).unwrap().rect.x, 300.0);
+        assert_eq!(check_range(ws.view(ViewHandle(2)).unwrap().rect.width, 250.0, 0.01), true);
+        assert_eq!(check_range(ws.view(ViewHandle(3)).unwrap().rect.width, 450.0, 0.01), true);
+
+        let line = ws.hovering_grid_line((500.0, 201.0)).unwrap();
+        assert_eq!((line.axis, line.index), (GridAxis::Rows, 0));
+        assert_eq!(ws.hovering_grid_line((100.0, 100.0)).is_none(), true);
+    }
+
+    #[test]
+    fn test_drag_grid_line_keeps_other_edges() {
+        let mut ws: Workspace = Workspace::from_layout(Rect::new(0.0, 0.0, 1000.0, 600.0), tabs(&[1, 2, 3])).unwrap();
+        ws.make_grid(ViewHandle(1), vec![Track::Fraction(1.0), Track::Fixed(200.0), Track::Fraction(1.0)],
+                     vec![Track::Fraction(1.0)]).unwrap();
+        ws.update();
+
+        // A fixed column followed by a fractional one
+        let line = ws.hovering_grid_line((601.0, 300.0)).unwrap();
+        assert_eq!(line.index, 1);
+        ws.drag_grid_line(line, (100.0, 0.0)).unwrap();
+        ws.update();
+
+        let rect = |ws: &Workspace, handle: u64| ws.view(ViewHandle(handle)).unwrap().rect;
+        assert_eq!(ws.grid(ViewHandle(1)).unwrap().columns[1], Track::Fixed(300.0));
+        assert_eq!(check_range(rect(&ws, 1).width, 400.0, 0.01), true);
+        assert_eq!(check_range(rect(&ws, 2).x, 400.0, 0.01), true);
+        assert_eq!(check_range(rect(&ws, 3).x, 700.0, 0.01), true);
+        assert_eq!(check_range(rect(&ws, 3).width, 300.0, 0.01), true);
+
+        // A fractional column followed by a fixed one
+        let line = ws.hovering_grid_line((401.0, 300.0)).unwrap();
+        assert_eq!(line.index, 0);
+        ws.drag_grid_line(line, (-100.0, 0.0)).unwrap();
+        ws.update();
+
+        assert_eq!(ws.grid(ViewHandle(1)).unwrap().columns[1], Track::Fixed(400.0));
+        assert_eq!(check_range(rect(&ws, 1).width, 300.0, 0.01), true);
+        assert_eq!(check_range(rect(&ws, 3).x, 700.0, 0.01), true);
+        assert_eq!(check_range(rect(&ws, 3).width, 300.0, 0.01), true);
+    }
+}
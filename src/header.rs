@@ -0,0 +1,231 @@
+use {Container, Rect, Split, Style, ViewHandle, Workspace};
+
+/// What is under a position in the header of a container
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TabHit {
+    /// The tab, or the title bar for a single view
+    Tab(ViewHandle),
+    /// The close button on the tab
+    Close(ViewHandle),
+}
+
+///
+/// The close button at the end of the tab, a square as high as the tab. It takes at most half of a
+/// narrow tab so the label can still be clicked. Views that can't be closed get an empty rect.
+///
+pub(crate) fn close_rect(tab_rect: Rect, closable: bool) -> Rect {
+    if !closable {
+        return Rect::default();
+    }
+
+    let size = tab_rect.height.min(tab_rect.width * 0.5);
+    Rect::new(tab_rect.x + tab_rect.width - size, tab_rect.y, size, tab_rect.height)
+}
+
+impl<T> Container<T> {
+    /// A single view gets a title bar and several views get a tab bar
+    fn header_height(&self, style: &Style) -> f32 {
+        if self.views.len() > 1 {
+            style.tab_bar_height
+        } else {
+            style.title_bar_height
+        }
+    }
+
+    ///
+    /// Splits the rect of the container into header and content and lays out the tabs in the
+    /// header. Tabs get the `tab_width` of their view and shrink evenly if they don't fit, a view
+    /// without a width gets an equal share of the header. Views that moved are added to `changed`.
+    /// A container with a grid lays out its views in the cells instead, see `update_grid`.
+    ///
+    pub(crate) fn update_header(&mut self, style: &Style, scale: f32, pixel_exact: bool,
+                                changed: &mut Vec<ViewHandle>) {
+        if self.grid.is_some() {
+            self.update_grid(style, scale, pixel_exact, changed);
+            return;
+        }
+
+        let rect = self.rect;
+        let mut height = self.header_height(style).min(rect.height);
+
+        if pixel_exact {
+            height = height.floor();
+        }
+
+        self.header = Rect::new(rect.x, rect.y, rect.width, height);
+        self.content = Rect::new(rect.x, rect.y + height, rect.width, rect.height - height);
+
+        let count = self.views.len() as f32;
+        let width_of = |width: f32| if width > 0.0 { width } else { rect.width / count };
+        let total: f32 = self.views.iter().map(|view| width_of(view.tab_width)).sum();
+        let shrink = if total > rect.width { rect.width / total } else { 1.0 };
+        let single = self.views.len() == 1;
+
+        let mut x = rect.x;
+
+        for view in &mut self.views {
+            let mut end = if single { rect.x + rect.width } else { x + width_of(view.tab_width) * shrink };
+
+            if pixel_exact {
+                end = end.round();
+            }
+
+            let tab_rect = Rect::new(x, rect.y, end - x, height);
+            let close_rect = close_rect(tab_rect, view.flags.closable);
+
+            if view.rect != self.content || view.tab_rect != tab_rect || view.close_rect != close_rect {
+                changed.push(view.handle);
+            }
+
+            view.tab_rect = tab_rect;
+            view.close_rect = close_rect;
+            view.rect = self.content;
+
+            x = end;
+        }
+    }
+
+    /// Returns the tab or close button at the position, views that can't be closed only have a tab
+    pub fn hit_tab(&self, pos: (f32, f32)) -> Option<TabHit> {
+        for view in &self.views {
+            if view.flags.closable && Split::is_inside(pos, view.close_rect) {
+                return Some(TabHit::Close(view.handle));
+            }
+
+            if Split::is_inside(pos, view.tab_rect) {
+                return Some(TabHit::Tab(view.handle));
+            }
+        }
+
+        None
+    }
+}
+
+impl<T> Split<T> {
+    fn hit_tab(&self, pos: (f32, f32)) -> Option<TabHit> {
+        for child in &self.children {
+            let hit = match child.split {
+                Some(ref split) => split.hit_tab(pos),
+                None => child.views.hit_tab(pos),
+            };
+
+            if hit.is_some() {
+                return hit;
+            }
+        }
+
+        None
+    }
+}
+
+impl<T> Workspace<T> {
+    ///
+    /// Returns the tab or close button at the position, using the rects from the last `update`.
+    /// Floating windows cover the docked containers below them.
+    ///
+    pub fn hit_tab(&self, pos: (f32, f32)) -> Option<TabHit> {
+        if let Some(window) = self.floating.iter().rev().find(|window| Split::is_inside(pos, window.rect)) {
+            return window.container.hit_tab(pos);
+        }
+
+        self.trees().filter_map(|split| split.hit_tab(pos)).next()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod test {
+    use test_util::check_range;
+    use {tabs, view, Layout, Rect, TabHit, ViewHandle, Workspace};
+
+    fn workspace() -> Workspace {
+        let layout = Layout::vertical(0.5).left(view(1)).right(tabs(&[2, 3, 4]));
+        Workspace::<()>::from_layout(Rect::new(0.0, 0.0, 1000.0, 500.0), layout).unwrap()
+    }
+
+    #[test]
+    fn test_header_and_content() {
+        let ws = workspace();
+        let split = ws.split.as_ref().unwrap();
+
+        let title = split.left_views();
+        assert_eq!(check_range(title.header.height, ws.style.title_bar_height, 0.01), true);
+        assert_eq!(check_range(title.content.y, ws.style.title_bar_height, 0.01), true);
+        assert_eq!(check_range(title.views[0].tab_rect.width, 500.0, 0.01), true);
+        assert_eq!(check_range(title.views[0].rect.height, 500.0 - ws.style.title_bar_height, 0.01), true);
+
+        let tabbed = split.right_views();
+        assert_eq!(check_range(tabbed.header.height, ws.style.tab_bar_height, 0.01), true);
+        assert_eq!(check_range(tabbed.content.height, 500.0 - ws.style.tab_bar_height, 0.01), true);
+    }
+
+    #[test]
+    fn test_tab_widths() {
+        let mut ws = workspace();
+
+        {
+            let views = &mut ws.split.as_mut().unwrap().right_views_mut().views;
+            views[0].tab_width = 100.0;
+            views[1].tab_width = 150.0;
+        }
+
+        ws.update();
+
+        {
+            let views = &ws.split.as_ref().unwrap().right_views().views;
+            assert_eq!(check_range(views[1].tab_rect.x, 600.0, 0.01), true);
+            assert_eq!(check_range(views[1].tab_rect.width, 150.0, 0.01), true);
+            // No width given so it gets an equal share of the header
+            assert_eq!(check_range(views[2].tab_rect.width, 500.0 / 3.0, 0.01), true);
+        }
+
+        ws.split.as_mut().unwrap().right_views_mut().views[1].tab_width = 300.0;
+        ws.update();
+
+        // Too wide, all tabs shrink to fit the header
+        let views = &ws.split.as_ref().unwrap().right_views().views;
+        assert_eq!(check_range(views[2].tab_rect.x + views[2].tab_rect.width, 1000.0, 0.01), true);
+        assert_eq!(views[0].tab_rect.width < 100.0, true);
+    }
+
+    #[test]
+    fn test_hit_tab() {
+        let mut ws = workspace();
+
+        for view in &mut ws.split.as_mut().unwrap().right_views_mut().views {
+            view.tab_width = 100.0;
+        }
+
+        ws.update();
+
+        assert_eq!(ws.hit_tab((650.0, 10.0)), Some(TabHit::Tab(ViewHandle(3))));
+        assert_eq!(ws.hit_tab((695.0, 10.0)), Some(TabHit::Close(ViewHandle(3))));
+        assert_eq!(ws.hit_tab((10.0, 5.0)), Some(TabHit::Tab(ViewHandle(1))));
+        assert_eq!(ws.hit_tab((850.0, 10.0)), None);
+        assert_eq!(ws.hit_tab((650.0, 100.0)), None);
+    }
+
+    #[test]
+    fn test_close_rect() {
+        let mut ws = workspace();
+
+        {
+            let views = &mut ws.split.as_mut().unwrap().right_views_mut().views;
+            views[0].tab_width = 30.0;
+            views[1].flags.closable = false;
+            views[1].tab_width = 100.0;
+        }
+
+        ws.update();
+
+        // A narrow tab keeps half of it for the label
+        let views = &ws.split.as_ref().unwrap().right_views().views;
+        assert_eq!(check_range(views[0].close_rect.width, 15.0, 0.01), true);
+        assert_eq!(ws.hit_tab((505.0, 10.0)), Some(TabHit::Tab(ViewHandle(2))));
+        assert_eq!(ws.hit_tab((525.0, 10.0)), Some(TabHit::Close(ViewHandle(2))));
+
+        // No close button on a view that can't be closed
+        assert_eq!(views[1].close_rect, Rect::default());
+        assert_eq!(ws.hit_tab((625.0, 10.0)), Some(TabHit::Tab(ViewHandle(3))));
+    }
+}
@@ -0,0 +1,287 @@
+use std::collections::HashSet;
+use {Child, Container, Direction, Error, Rect, Result, Split, View, ViewHandle, Workspace};
+
+enum Node {
+    Views(Vec<ViewHandle>),
+    Split {
+        direction: Direction,
+        ratio: f32,
+        left: Option<Box<Layout>>,
+        right: Option<Box<Layout>>,
+    },
+}
+
+///
+/// Description of a layout that can be turned into a `Workspace` in one step
+///
+/// ```
+/// use viewdock::{view, Layout, Rect, Workspace};
+///
+/// let layout = Layout::vertical(0.2)
+///     .left(view(1))
+///     .right(Layout::horizontal(0.7).left(view(2)).right(view(3)));
+///
+/// let ws: Workspace = Workspace::from_layout(Rect::new(0.0, 0.0, 1024.0, 768.0), layout).unwrap();
+/// ```
+///
+pub struct Layout {
+    node: Node,
+}
+
+/// Layout with a single container holding the view
+pub fn view<H: Into<ViewHandle>>(handle: H) -> Layout {
+    Layout { node: Node::Views(vec![handle.into()]) }
+}
+
+/// Layout with a single container holding all the views as tabs
+pub fn tabs<H: Into<ViewHandle> + Copy>(handles: &[H]) -> Layout {
+    Layout { node: Node::Views(handles.iter().map(|&handle| handle.into()).collect()) }
+}
+
+impl Layout {
+    /// A split with left and right side. The ratio is the share of the left side
+    pub fn vertical(ratio: f32) -> Layout {
+        Self::split(Direction::Vertical, ratio)
+    }
+
+    /// A split with top and bottom side. The ratio is the share of the top side
+    pub fn horizontal(ratio: f32) -> Layout {
+        Self::split(Direction::Horizontal, ratio)
+    }
+
+    fn split(direction: Direction, ratio: f32) -> Layout {
+        Layout {
+            node: Node::Split {
+                direction,
+                ratio,
+                left: None,
+                right: None,
+            }
+        }
+    }
+
+    /// Sets the left/top side of a split. Has no effect on a view layout.
+    pub fn left<L: Into<Layout>>(mut self, layout: L) -> Layout {
+        if let Node::Split { ref mut left, .. } = self.node {
+            *left = Some(Box::new(layout.into()));
+        }
+        self
+    }
+
+    /// Sets the right/bottom side of a split. Has no effect on a view layout.
+    pub fn right<L: Into<Layout>>(mut self, layout: L) -> Layout {
+        if let Node::Split { ref mut right, .. } = self.node {
+            *right = Some(Box::new(layout.into()));
+        }
+        self
+    }
+
+    /// Alias of `left` that reads better for horizontal splits
+    pub fn top<L: Into<Layout>>(self, layout: L) -> Layout {
+        self.left(layout)
+    }
+
+    /// Alias of `right` that reads better for horizontal splits
+    pub fn bottom<L: Into<Layout>>(self, layout: L) -> Layout {
+        self.right(layout)
+    }
+
+    fn into_side<T: Default>(self, ws: &mut Workspace<T>, seen: &mut HashSet<ViewHandle>)
+                             -> Result<(Child<T>, Container<T>)> {
+        match self.node {
+            Node::Views(handles) => {
+                if handles.is_empty() {
+                    return Err(Error::ConstraintViolation("a container needs at least one view".to_owned()));
+                }
+
+                let mut container = Container::new();
+
+                for handle in handles {
+                    if !seen.insert(handle) {
+                        return Err(Error::DuplicateHandle(handle));
+                    }
+
+                    container.views.push(View::new(handle, T::default()));
+                }
+
+                Ok((None, container))
+            }
+
+            Node::Split { direction, ratio, left, right } => {
+                if !(ratio > 0.0 && ratio < 1.0) {
+                    return Err(Error::ConstraintViolation(format!("ratio {} has to be within (0.0, 1.0)", ratio)));
+                }
+
+                let (left, right) = match (left, right) {
+                    (Some(left), Some(right)) => (left, right),
+                    _ => return Err(Error::ConstraintViolation("a split needs both sides".to_owned())),
+                };
+
+                let mut split = Box::new(Split::new(direction, ws.next_split_handle()));
+                split.set_ratio(ratio);
+                split.default_ratio = ratio;
+
+                for (child, side) in split.children.iter_mut().zip([left, right]) {
+                    let (nested, container) = side.into_side(ws, seen)?;
+                    child.split = nested;
+                    child.views = container;
+                }
+
+                Ok((Some(split), Container::new()))
+            }
+        }
+    }
+}
+
+impl From<ViewHandle> for Layout {
+    fn from(handle: ViewHandle) -> Layout {
+        view(handle)
+    }
+}
+
+/// Commonly used layouts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Preset {
+    /// Explorer to the left, editor in the center with an output panel below and properties to the right
+    Ide,
+    /// Three equally sized columns
+    ThreePane,
+    /// Two rows with two views each
+    Grid2x2,
+}
+
+impl Preset {
+    /// Number of views the preset expects
+    pub fn view_count(self) -> usize {
+        match self {
+            Preset::Ide => 4,
+            Preset::ThreePane => 3,
+            Preset::Grid2x2 => 4,
+        }
+    }
+
+    ///
+    /// Builds the layout of the preset. The views are assigned in reading order, for `Preset::Ide`
+    /// that is explorer, editor, output and properties.
+    ///
+    pub fn layout(self, views: &[ViewHandle]) -> Result<Layout> {
+        if views.len() != self.view_count() {
+            return Err(Error::ConstraintViolation(format!("{:?} preset expects {} views but got {}",
+                                                          self, self.view_count(), views.len())));
+        }
+
+        Ok(match self {
+            Preset::Ide => {
+                Layout::vertical(0.2)
+                    .left(views[0])
+                    .right(Layout::vertical(0.75)
+                        .left(Layout::horizontal(0.7).top(views[1]).bottom(views[2]))
+                        .right(views[3]))
+            }
+
+            Preset::ThreePane => {
+                Layout::vertical(1.0 / 3.0)
+                    .left(views[0])
+                    .right(Layout::vertical(0.5).left(views[1]).right(views[2]))
+            }
+
+            Preset::Grid2x2 => {
+                Layout::horizontal(0.5)
+                    .top(Layout::vertical(0.5).left(views[0]).right(views[1]))
+                    .bottom(Layout::vertical(0.5).left(views[2]).right(views[3]))
+            }
+        })
+    }
+}
+
+impl<T> Workspace<T> {
+    /// Construct a new workspace with the layout. Fails if the layout is incomplete or invalid.
+    pub fn from_layout(rect: Rect, layout: Layout) -> Result<Workspace<T>> where T: Default {
+        let mut ws = Workspace::new(rect)?;
+        let mut seen = HashSet::new();
+
+        let (split, container) = layout.into_side(&mut ws, &mut seen)?;
+
+        ws.split = match split {
+            Some(split) => Some(split),
+            None => Some(Box::new(Split::full(ws.next_split_handle(), container))),
+        };
+
+        ws.validate()?;
+        ws.update();
+
+        Ok(ws)
+    }
+
+    /// Construct a new workspace from one of the presets
+    pub fn from_preset(rect: Rect, preset: Preset, views: &[ViewHandle]) -> Result<Workspace<T>> where T: Default {
+        Self::from_layout(rect, preset.layout(views)?)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod test {
+    use {tabs, view, Direction, Error, Layout, Preset, Rect, ViewHandle, Workspace};
+
+    fn rect() -> Rect {
+        Rect::new(0.0, 0.0, 1000.0, 1000.0)
+    }
+
+    #[test]
+    fn test_layout_single_view() {
+        let ws: Workspace = Workspace::from_layout(rect(), view(1)).unwrap();
+        let split = ws.split.unwrap();
+
+        assert_eq!(split.direction, Direction::Full);
+        assert_eq!(split.left_views().views[0].handle, ViewHandle(1));
+    }
+
+    #[test]
+    fn test_layout_nested() {
+        let layout = Layout::vertical(0.2)
+            .left(tabs(&[1, 2]))
+            .right(Layout::horizontal(0.7).top(view(3)).bottom(view(4)));
+
+        let ws: Workspace = Workspace::from_layout(rect(), layout).unwrap();
+        let split = ws.split.as_ref().unwrap();
+
+        assert_eq!(split.left_views().views.len(), 2);
+        assert_eq!(split.left_views().rect.width, 200.0);
+
+        let right = split.right().as_ref().unwrap();
+        assert_eq!(right.direction, Direction::Horizontal);
+        assert_eq!(right.left_views().rect.height, 700.0);
+        assert_eq!(right.right_views().views[0].handle, ViewHandle(4));
+    }
+
+    #[test]
+    fn test_layout_errors() {
+        match Workspace::<()>::from_layout(rect(), Layout::vertical(0.5).left(view(1))) {
+            Err(Error::ConstraintViolation(_)) => (),
+            _ => panic!("expected missing side to fail"),
+        }
+
+        match Workspace::<()>::from_layout(rect(), Layout::vertical(0.5).left(view(1)).right(view(1))) {
+            Err(Error::DuplicateHandle(ViewHandle(1))) => (),
+            _ => panic!("expected duplicate view to fail"),
+        }
+
+        assert_eq!(Workspace::<()>::from_layout(rect(), Layout::vertical(1.5).left(view(1)).right(view(2))).is_err(), true);
+    }
+
+    #[test]
+    fn test_presets() {
+        let views = [ViewHandle(1), ViewHandle(2), ViewHandle(3), ViewHandle(4)];
+
+        assert_eq!(Workspace::<()>::from_preset(rect(), Preset::Ide, &views).is_ok(), true);
+        assert_eq!(Workspace::<()>::from_preset(rect(), Preset::Grid2x2, &views).is_ok(), true);
+        assert_eq!(Workspace::<()>::from_preset(rect(), Preset::ThreePane, &views).is_err(), true);
+
+        let ws: Workspace = Workspace::from_preset(rect(), Preset::ThreePane, &views[..3]).unwrap();
+
+        for view in &views[..3] {
+            assert_eq!((ws.view(*view).unwrap().rect.width - 1000.0 / 3.0).abs() < 0.01, true);
+        }
+    }
+}
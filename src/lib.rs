@@ -1,15 +1,79 @@
+use std::collections::HashMap;
+use std::mem;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use animate::Transition;
+use dock::DockZones;
+use snapshot::Snapshot;
+
+mod animate;
+#[cfg(feature = "constraints")]
+mod constraint;
+mod dirty;
+mod dock;
+mod dot;
+mod drop;
+mod equalize;
 mod error;
-pub use self::error::Error;
+mod float;
+mod gesture;
+mod grid;
+mod header;
+mod layout;
+mod manager;
+mod meta;
+mod multi;
+mod persist;
+mod pixel;
+mod rearrange;
+mod resize;
+mod rules;
+mod snap;
+mod snapshot;
+#[cfg(feature = "constraints")]
+mod solver;
+mod style;
+#[cfg(test)]
+mod test_util;
+mod validate;
+pub use self::animate::{Animation, Easing};
+#[cfg(feature = "constraints")]
+pub use self::constraint::{EqualSizes, Length, SizeAxis, SizeConstraint, Strength, ViewConstraints};
+pub use self::dock::{DockArea, DockZone, ViewKind};
+pub use self::drop::DropZone;
+pub use self::error::{Error, TreeError};
+pub use self::float::FloatingWindow;
+pub use self::gesture::{DropPreview, TabDrag};
+pub use self::grid::{Grid, GridAxis, GridCell, GridLine, Track};
+pub use self::header::TabHit;
+pub use self::layout::{tabs, view, Layout, Preset};
+pub use self::manager::{WorkspaceHandle, WorkspaceManager};
+pub use self::meta::ViewFlags;
+pub use self::pixel::IntRect;
+pub use self::resize::ResizeStrategy;
+pub use self::rules::{DockRules, DropSide, DropTarget};
+pub use self::snap::SnapConfig;
+pub use self::snapshot::LayoutDiff;
+pub use self::style::Style;
 
 pub type Result<T> = std::result::Result<T, Error>;
 
-#[derive(Clone, Copy)]
+/// A child split of a side, None if the side holds a container instead
+type Child<T> = Option<Box<Split<T>>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct ViewHandle(pub u64);
 
-#[derive(Clone, Copy)]
+impl From<u64> for ViewHandle {
+    fn from(handle: u64) -> ViewHandle {
+        ViewHandle(handle)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct SplitHandle(pub u64);
 
-#[derive(Debug, Default, Clone, Copy)]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
 pub struct Rect {
     pub x: f32,
     pub y: f32,
@@ -20,30 +84,75 @@ pub struct Rect {
 impl Rect {
     pub fn new(x: f32, y: f32, width: f32, height: f32) -> Rect {
         Rect {
-            x: x,
-            y: y,
-            width: width,
-            height: height
+            x,
+            y,
+            width,
+            height,
         }
     }
 }
 
+/// A view in the workspace. `T` is user data owned by the view, see `Workspace::views`
 #[derive(Clone)]
-pub struct View {
+pub struct View<T = ()> {
     pub handle: ViewHandle,
-    pub rect: Rect
+    /// Title shown in the title or tab bar
+    pub title: String,
+    /// What the user is allowed to do with the view
+    pub flags: ViewFlags,
+    /// Documents stay in the document area, tools can be docked anywhere
+    pub kind: ViewKind,
+    /// Size (in logical units) the view gets when it's docked next to another view
+    pub preferred_size: Option<(f32, f32)>,
+    /// Limits of the container size for the constraint layout, see `Workspace::set_constraints`
+    #[cfg(feature = "constraints")]
+    constraints: ViewConstraints,
+    /// Content rect of the container the view is in
+    pub rect: Rect,
+    /// Width of the tab (in pixels), usually measured from the title. 0.0 shares the header equally
+    pub tab_width: f32,
+    /// Rect of the tab in the header of the container, or the whole title bar for a single view
+    pub tab_rect: Rect,
+    /// Rect of the close button at the end of the tab, empty if the view can't be closed
+    pub close_rect: Rect,
+
+    payload: T,
 }
 
-impl View {
-    fn new(view_handle: ViewHandle) -> View {
+impl<T> View<T> {
+    pub fn new(view_handle: ViewHandle, payload: T) -> View<T> {
         View {
             handle: view_handle,
+            title: String::new(),
+            flags: ViewFlags::default(),
+            kind: ViewKind::Tool,
+            preferred_size: None,
+            #[cfg(feature = "constraints")]
+            constraints: ViewConstraints::default(),
             rect: Rect::default(),
+            tab_width: 0.0,
+            tab_rect: Rect::default(),
+            close_rect: Rect::default(),
+            payload,
         }
     }
+
+    /// The user data owned by the view
+    pub fn payload(&self) -> &T {
+        &self.payload
+    }
+
+    pub fn payload_mut(&mut self) -> &mut T {
+        &mut self.payload
+    }
+
+    /// Consumes the view and returns its user data
+    pub fn into_payload(self) -> T {
+        self.payload
+    }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Direction {
     Vertical,
     Horizontal,
@@ -51,98 +160,108 @@ pub enum Direction {
 }
 
 #[derive(Clone)]
-pub struct Container {
-    pub views: Vec<View>,
+pub struct Container<T = ()> {
+    pub views: Vec<View<T>>,
     pub rect: Rect,
+    /// Title or tab bar at the top of `rect`
+    pub header: Rect,
+    /// The part of `rect` below the header
+    pub content: Rect,
+    /// Keep the size (in pixels) of the container when the workspace is resized with `ResizeStrategy::KeepFixed`
+    pub fixed: bool,
+    /// Lays out the views in the cells of a grid instead of as tabs, see `Workspace::make_grid`
+    pub grid: Option<Grid>,
 }
 
-impl Container {
-    pub fn new() -> Container {
+impl<T> Container<T> {
+    pub fn new() -> Container<T> {
         Container {
             views: Vec::new(),
             rect: Rect::default(),
+            header: Rect::default(),
+            content: Rect::default(),
+            fixed: false,
+            grid: None,
         }
     }
-
 }
 
-pub struct Split {
-    /// left/top slipit 
-    pub left: Option<Box<Split>>,
-    /// right/bottom split
-    pub right: Option<Box<Split>>,
-    /// left/top views
-    pub left_views: Container,
-    /// right/top views
-    pub right_views: Container,
-    /// ratioage value of how much of each side that is visible. 1.0 = right/bottom fully visible
-    pub ratio: f32,
-    /// Direction of the split
-    pub direction: Direction,
-    /// Handle of the spliter
-    pub handle: SplitHandle,
+impl<T> Default for Container<T> {
+    fn default() -> Container<T> {
+        Container::new()
+    }
+}
 
-    rect: Rect,
+/// Weights are never allowed to reach 0.0 or 1.0 so every child of a split stays visible
+pub(crate) const MIN_RATIO: f32 = 0.001;
+
+/// One child of a split, either a nested split or a container holding views
+pub struct SplitChild<T = ()> {
+    /// Nested split, None if the child holds `views` instead
+    pub split: Child<T>,
+    /// Views of the child if it isn't split any further
+    pub views: Container<T>,
+    /// Share of the space along the direction of the split. The weights of a split sum up to 1.0
+    pub weight: f32,
 }
 
-impl Split {
-    pub fn new(direction: Direction, handle: SplitHandle) -> Split {
-        Split {
-            left: None,
-            right: None,
-            left_views: Container::new(),
-            right_views: Container::new(),
-            ratio: 0.0,
-            direction: direction,
-            handle: handle,
-            rect: Rect::default(),
+impl<T> SplitChild<T> {
+    /// An empty child with the weight
+    pub fn new(weight: f32) -> SplitChild<T> {
+        SplitChild {
+            split: None,
+            views: Container::new(),
+            weight,
         }
     }
 
-    pub fn no_split(&mut self, direction: Direction, view_handle: ViewHandle) -> bool {
-        self.direction = direction;
-
-        if self.left_views.views.len() == 0 {
-            self.left_views.views.push(View::new(view_handle));
-            self.ratio = 0.5;
-            return true;
-        }
-
-        if self.right_views.views.len() == 0 {
-            self.right_views.views.push(View::new(view_handle));
-            self.ratio = 0.5;
-            return true;
+    /// A child holding the container
+    pub fn with_views(views: Container<T>, weight: f32) -> SplitChild<T> {
+        SplitChild {
+            split: None,
+            views,
+            weight,
         }
-
-        false
     }
 
-    pub fn split_left(&mut self, split_handle: SplitHandle, view_handle: ViewHandle, direction: Direction) {
-        if Self::no_split(self, direction, view_handle) { 
-            return; 
-        } else {
-            let mut split = Box::new(Split::new(direction, split_handle));
-            split.right_views = self.left_views.clone();
-            split.left_views.views.push(View::new(view_handle));
-            split.ratio = 0.5;
-            self.left = Some(split);
-            self.left_views.views.clear();
-        }
+    /// True if the child has neither a split nor views
+    fn is_empty(&self) -> bool {
+        self.split.is_none() && self.views.views.is_empty()
     }
 
-    pub fn split_right(&mut self, split_handle: SplitHandle, view_handle: ViewHandle, direction: Direction) {
-        if Self::no_split(self, direction, view_handle) { 
-            return; 
-        } else {
-            let mut split = Box::new(Split::new(direction, split_handle));
-            split.left_views = self.right_views.clone();
-            split.right_views.views.push(View::new(view_handle));
-            split.ratio = 0.5;
-            self.right = Some(split);
-            self.right_views.views.clear();
-        }
+    fn has_view(&self, handle: ViewHandle) -> bool {
+        self.views.views.iter().any(|view| view.handle == handle) ||
+            self.split.as_ref().is_some_and(|split| split.has_view(handle))
     }
+}
 
+///
+/// A node of the docking tree with its children laid out next to each other in one direction.
+/// Most splits have two children, the `left`/`right` accessors and `ratio` cover those. Splits made
+/// with `Workspace::split_by_view_handle_n` have more and dragging the sizer between two neighbours
+/// only resizes those two. The accessors replace the `left`, `right`, `left_views`, `right_views`
+/// and `ratio` fields of earlier versions, see the README for the migration.
+///
+pub struct Split<T = ()> {
+    /// Children in left to right or top to bottom order. A split always has at least two, a
+    /// `Direction::Full` split only uses the first one. Call `mark_dirty` after changing them here
+    pub children: Vec<SplitChild<T>>,
+    /// Ratio the sizer goes back to when it's reset, for example on double-click
+    pub default_ratio: f32,
+    /// Direction of the split, call `mark_dirty` after changing it here
+    pub direction: Direction,
+    /// Handle of the spliter
+    pub handle: SplitHandle,
+
+    rect: Rect,
+    /// Distance between the pointer and the sizer while it's locked to a snap point
+    drag_offset: f32,
+    /// The split or a split below it changed since the last layout, see `mark_dirty`
+    dirty: bool,
+}
+
+/// Geometry helpers that don't depend on the user data type
+impl Split {
     pub fn calc_horizontal_sizing(rect: Rect, ratio: f32) -> (Rect, Rect) {
         let h = rect.height * ratio;
 
@@ -161,171 +280,460 @@ impl Split {
         (rect_left, rect_right)
     }
 
-    fn calc_rects(direction: Direction, rect: Rect, ratio: f32) -> (Rect, Rect) {
+    fn is_inside(v: (f32, f32), rect: Rect) -> bool {
+        let x0 = rect.x;
+        let y0 = rect.y;
+        let x1 = x0 + rect.width;
+        let y1 = y0 + rect.height;
+
+        (v.0 >= x0 && v.0 < x1) && (v.1 >= y0 && v.1 < y1)
+    }
+
+    fn get_sizer_from_rect_horizontal(rect: Rect, size: f32) -> Rect {
+        Rect::new(rect.x, rect.y + rect.height, rect.width, size)
+    }
+
+    fn get_sizer_from_rect_vertical(rect: Rect, size: f32) -> Rect {
+        Rect::new(rect.x + rect.width, rect.y, size, rect.height)
+    }
+
+    fn is_hovering_rect(pos: (f32, f32), border_size: f32, rect: Rect, direction: Direction) -> bool {
         match direction {
-            Direction::Vertical => Self::calc_vertical_sizing(rect, ratio),
-            Direction::Horizontal => Self::calc_horizontal_sizing(rect, ratio),
-            Direction::Full => (rect, rect),
+            Direction::Horizontal => Split::is_inside(pos, Split::get_sizer_from_rect_horizontal(rect, border_size)),
+            Direction::Vertical => Split::is_inside(pos, Split::get_sizer_from_rect_vertical(rect, border_size)),
+            Direction::Full => false, 
         }
     }
+}
 
-    fn recursive_update(&mut self, rect: Rect, level: usize) {
-        let rects = Self::calc_rects(self.direction, rect, self.ratio);
+impl<T> Split<T> {
+    pub fn new(direction: Direction, handle: SplitHandle) -> Split<T> {
+        Self::with_children(direction, handle, 2)
+    }
 
-        self.rect = rect;
+    /// A split with `count` empty children of the same weight, at least two
+    pub fn with_children(direction: Direction, handle: SplitHandle, count: usize) -> Split<T> {
+        let count = count.max(2);
 
-        if let Some(ref mut split) = self.left {
-            Self::recursive_update(split, rects.0, level + 1);
+        Split {
+            children: (0..count).map(|_| SplitChild::new(1.0 / count as f32)).collect(),
+            default_ratio: 0.5,
+            direction,
+            handle,
+            rect: Rect::default(),
+            drag_offset: 0.0,
+            dirty: true,
         }
+    }
+
+    /// A `Direction::Full` split holding the container
+    pub(crate) fn full(handle: SplitHandle, container: Container<T>) -> Split<T> {
+        let mut split = Split::new(Direction::Full, handle);
+        split.set_ratio(1.0);
+        split.children[0].views = container;
+        split
+    }
+
+    fn last(&self) -> &SplitChild<T> {
+        &self.children[self.children.len() - 1]
+    }
 
-        if let Some(ref mut split) = self.right {
-            Self::recursive_update(split, rects.1, level + 1);
+    fn last_mut(&mut self) -> &mut SplitChild<T> {
+        let last = self.children.len() - 1;
+        &mut self.children[last]
+    }
+
+    /// left/top split, same as the split of the first child
+    pub fn left(&self) -> &Child<T> {
+        &self.children[0].split
+    }
+
+    pub fn left_mut(&mut self) -> &mut Child<T> {
+        self.dirty = true;
+        &mut self.children[0].split
+    }
+
+    /// right/bottom split, same as the split of the last child
+    pub fn right(&self) -> &Child<T> {
+        &self.last().split
+    }
+
+    pub fn right_mut(&mut self) -> &mut Child<T> {
+        self.dirty = true;
+        &mut self.last_mut().split
+    }
+
+    /// left/top views
+    pub fn left_views(&self) -> &Container<T> {
+        &self.children[0].views
+    }
+
+    pub fn left_views_mut(&mut self) -> &mut Container<T> {
+        self.dirty = true;
+        &mut self.children[0].views
+    }
+
+    /// right/bottom views
+    pub fn right_views(&self) -> &Container<T> {
+        &self.last().views
+    }
+
+    pub fn right_views_mut(&mut self) -> &mut Container<T> {
+        self.dirty = true;
+        &mut self.last_mut().views
+    }
+
+    /// Share of the left/top side, the weight of the first child
+    pub fn ratio(&self) -> f32 {
+        self.children[0].weight
+    }
+
+    /// Sets the share of the left/top side. The other children share the rest in their current proportions
+    pub fn set_ratio(&mut self, ratio: f32) {
+        self.set_share(0, ratio);
+    }
+
+    /// Sets the weight of the child and scales the others so the weights still sum up to 1.0
+    pub(crate) fn set_share(&mut self, index: usize, share: f32) {
+        let rest: f32 = self.children.iter().enumerate()
+            .filter(|&(other, _)| other != index)
+            .map(|(_, child)| child.weight)
+            .sum();
+        let count = (self.children.len() - 1) as f32;
+
+        self.dirty = true;
+
+        for (other, child) in self.children.iter_mut().enumerate() {
+            child.weight = if other == index {
+                share
+            } else if rest > 0.0 {
+                child.weight / rest * (1.0 - share)
+            } else {
+                (1.0 - share) / count
+            };
         }
+    }
+
+    /// Gives every child the same share
+    pub(crate) fn set_equal_weights(&mut self) {
+        let weight = 1.0 / self.children.len() as f32;
+        self.dirty = true;
+        self.children.iter_mut().for_each(|child| child.weight = weight);
+    }
+
+    /// Rects of the children when the split is laid out in `rect`
+    pub(crate) fn child_rects(&self, rect: Rect, pixel_exact: bool) -> Vec<Rect> {
+        let extent = match self.direction {
+            Direction::Vertical => rect.width,
+            Direction::Horizontal => rect.height,
+            Direction::Full => 0.0,
+        };
 
-        self.left_views.rect = rects.0;
-        self.right_views.rect = rects.1;
+        let last = self.children.len() - 1;
+        let mut offset = 0.0;
 
-        //println!("level {} left  - x: {} y: {} w: {} h: {}", level, rects.0.x, rects.0.y, rects.0.width, rects.0.height);
-        //println!("level {} right - x: {} y: {} w: {} h: {}", level, rects.1.x, rects.1.y, rects.1.width, rects.1.height);
+        self.children.iter().enumerate().map(|(index, child)| {
+            // The last child takes what is left so the children always fill the rect
+            let size = if index == last { extent - offset } else { extent * child.weight };
+
+            let child_rect = match self.direction {
+                Direction::Vertical => Rect::new(rect.x + offset, rect.y, size, rect.height),
+                Direction::Horizontal => Rect::new(rect.x, rect.y + offset, rect.width, size),
+                Direction::Full => rect,
+            };
+
+            offset += size;
+
+            if pixel_exact { child_rect.round() } else { child_rect }
+        }).collect()
+    }
+
+    /// Puts the view into a free child of the split. Returns the view if all children are taken
+    pub fn no_split(&mut self, direction: Direction, view: View<T>) -> Option<View<T>> {
+        match self.children.iter_mut().find(|child| child.is_empty()) {
+            Some(child) => child.views.views.push(view),
+            None => return Some(view),
+        }
+
+        self.direction = direction;
+        self.set_equal_weights();
 
-        // TODO: Remove these loops, should be propagated to update call only
+        None
+    }
 
-        for view in &mut self.left_views.views {
-            //println!("level {} left  - x: {} y: {} w: {} h: {}", level, rects.0.x, rects.0.y, rects.0.width, rects.0.height);
-            view.rect = rects.0;
+    pub fn split_left(&mut self, split_handle: SplitHandle, view: View<T>, direction: Direction) {
+        if let Some(view) = Self::no_split(self, direction, view) {
+            self.split_child(0, split_handle, view, direction, true);
         }
+    }
 
-        for view in &mut self.right_views.views {
-            //println!("level {} right - x: {} y: {} w: {} h: {}", level, rects.1.x, rects.1.y, rects.1.width, rects.1.height);
-            view.rect = rects.1;
+    pub fn split_right(&mut self, split_handle: SplitHandle, view: View<T>, direction: Direction) {
+        if let Some(view) = Self::no_split(self, direction, view) {
+            let last = self.children.len() - 1;
+            self.split_child(last, split_handle, view, direction, false);
         }
     }
 
-    pub fn split_by_view_handle(&mut self, direction: Direction, split_handle: SplitHandle, find_handle: ViewHandle, handle: ViewHandle) {
-        // TODO: Fix me
-        let left_views = self.left_views.views.clone();
-        let right_views = self.right_views.views.clone();
+    /// Replaces the child with a split holding the view and what the child held, the view going first or last
+    fn split_child(&mut self, index: usize, split_handle: SplitHandle, view: View<T>, direction: Direction, first: bool) {
+        self.dirty = true;
+
+        let child = &mut self.children[index];
+        let (old, new) = if first { (1, 0) } else { (0, 1) };
+
+        let mut split = Box::new(Split::new(direction, split_handle));
+        split.children[old].split = child.split.take();
+        split.children[old].views = mem::take(&mut child.views);
+        split.children[new].views.views.push(view);
+
+        child.split = Some(split);
+    }
+
+    fn recursive_update(&mut self, rect: Rect, style: &Style, scale: f32, pixel_exact: bool,
+                        changed: &mut Vec<ViewHandle>) {
+        let rects = self.child_rects(rect, pixel_exact);
+
+        self.rect = rect;
 
-        for view in left_views {
-            if view.handle.0 == find_handle.0 {
-                self.split_left(split_handle, handle, direction);
-                return;
+        for (child, rect) in self.children.iter_mut().zip(rects) {
+            if let Some(ref mut split) = child.split {
+                Self::recursive_update(split, rect, style, scale, pixel_exact, changed);
             }
+
+            child.views.rect = rect;
+            child.views.update_header(style, scale, pixel_exact, changed);
         }
 
-        for view in right_views {
-            if view.handle.0 == find_handle.0 {
-                self.split_right(split_handle, handle, direction);
-                return;
+        self.dirty = false;
+    }
+
+    /// Splits the container holding `find_handle` and puts the view next to it. Returns the view
+    /// if `find_handle` wasn't found
+    pub fn split_by_view_handle(&mut self, direction: Direction, split_handle: SplitHandle, find_handle: ViewHandle,
+                                view: View<T>) -> Option<View<T>> {
+        let found = self.children.iter()
+            .position(|child| child.views.views.iter().any(|view| view.handle == find_handle));
+
+        if let Some(index) = found {
+            if let Some(view) = self.no_split(direction, view) {
+                self.split_child(index, split_handle, view, direction, index == 0);
             }
-        }
 
-        if let Some(ref mut split) = self.left {
-            Self::split_by_view_handle(split, direction, split_handle, find_handle, handle); 
+            return None;
         }
 
-        if let Some(ref mut split) = self.right {
-            Self::split_by_view_handle(split, direction, split_handle, find_handle, handle); 
+        let mut view = view;
+
+        for index in 0..self.children.len() {
+            if let Some(ref mut split) = self.children[index].split {
+                match Self::split_by_view_handle(split, direction, split_handle, find_handle, view) {
+                    Some(back) => view = back,
+                    None => {
+                        self.dirty = true;
+                        return None;
+                    }
+                }
+            }
         }
+
+        Some(view)
     }
 
-    fn is_inside(v: (f32, f32), rect: Rect) -> bool {
-        let x0 = rect.x;
-        let y0 = rect.y;
-        let x1 = x0 + rect.width;
-        let y1 = y0 + rect.height;
+    fn has_view(&self, handle: ViewHandle) -> bool {
+        self.children.iter().any(|child| child.has_view(handle))
+    }
 
-        if (v.0 >= x0 && v.0 < x1) && (v.1 >= y0 && v.1 < y1) {
-            true
-        } else {
-            false
+    /// Finds the split with the handle in this split or any of its children
+    pub fn find_split(&self, handle: SplitHandle) -> Option<&Split<T>> {
+        if self.handle == handle {
+            return Some(self);
         }
+
+        self.children.iter()
+            .filter_map(|child| child.split.as_ref())
+            .find_map(|split| split.find_split(handle))
     }
 
-    fn get_sizer_from_rect_horizontal(rect: Rect, size: f32) -> Rect {
-        Rect::new(rect.x, rect.y + rect.height, rect.width, size)
+    ///
+    /// Finds the split with the handle in this split or any of its children. The split and the
+    /// splits above it are marked dirty as the caller is going to change it.
+    ///
+    pub fn find_split_mut(&mut self, handle: SplitHandle) -> Option<&mut Split<T>> {
+        if !self.has_split(handle) {
+            return None;
+        }
+
+        self.dirty = true;
+
+        if self.handle == handle {
+            return Some(self);
+        }
+
+        self.children.iter_mut()
+            .filter_map(|child| child.split.as_mut())
+            .find(|split| split.has_split(handle))
+            .and_then(|split| split.find_split_mut(handle))
     }
 
-    fn get_sizer_from_rect_vertical(rect: Rect, size: f32) -> Rect {
-        Rect::new(rect.x + rect.width, rect.y, size, rect.height)
+    /// Takes out the children without views and gives their space to the others
+    fn remove_empty_children(&mut self) {
+        self.children.retain(|child| !child.is_empty());
+        self.dirty = true;
+
+        let total: f32 = self.children.iter().map(|child| child.weight).sum();
+
+        if total > 0.0 {
+            self.children.iter_mut().for_each(|child| child.weight /= total);
+        }
     }
 
-    fn is_hovering_rect(pos: (f32, f32), border_size: f32, rect: Rect, direction: Direction) -> bool {
-        match direction {
-            Direction::Horizontal => Self::is_inside(pos, Self::get_sizer_from_rect_horizontal(rect, border_size)),
-            Direction::Vertical => Self::is_inside(pos, Self::get_sizer_from_rect_vertical(rect, border_size)),
-            Direction::Full => false, 
+    /// Replaces a child split that is left with a single non-empty child with that child
+    fn collapse_child(child: &mut SplitChild<T>) {
+        let mut split = match child.split.take() {
+            Some(split) => split,
+            None => return,
+        };
+
+        split.remove_empty_children();
+
+        if split.children.len() > 1 {
+            child.split = Some(split);
+        } else if let Some(remaining) = split.children.pop() {
+            child.split = remaining.split;
+            child.views = remaining.views;
         }
     }
 
-    pub fn is_hovering_sizer(&self, pos: (f32, f32)) -> Option<SplitHandle> {
-        if Self::is_hovering_rect(pos, 8.0, self.left_views.rect, self.direction) {
-            return Some(self.handle) 
+    fn remove_view(&mut self, handle: ViewHandle) -> Option<View<T>> {
+        if !self.has_view(handle) {
+            return None;
         }
 
-        if let Some(ref split) = self.left {
-            if let Some(handle) = Self::is_hovering_sizer(split, pos) {
-                return Some(handle);
+        self.dirty = true;
+
+        for child in &mut self.children {
+            if let Some(index) = child.views.views.iter().position(|view| view.handle == handle) {
+                return Some(child.views.views.remove(index));
             }
         }
 
-        if let Some(ref split) = self.right {
-            if let Some(handle) = Self::is_hovering_sizer(split, pos) {
-                return Some(handle);
+        for child in &mut self.children {
+            if let Some(view) = child.split.as_mut().and_then(|split| split.remove_view(handle)) {
+                Self::collapse_child(child);
+                return Some(view);
             }
         }
 
         None
     }
 
-    fn map_rect_to_delta(&self, delta: (f32, f32)) -> f32 {
-        match self.direction {
-            Direction::Vertical => -delta.0 / self.rect.width,
-            Direction::Horizontal => -delta.1 / self.rect.height,
-            _ => 0.0,
-        }
+    /// Returns the split whose sizer is at the position. Sizers are 8.0 thick without hit padding
+    pub fn is_hovering_sizer(&self, pos: (f32, f32)) -> Option<SplitHandle> {
+        let style = Style { sizer_hit_padding: 0.0, ..Style::default() };
+        self.hovering_sizer(pos, &style).map(|(handle, _)| handle)
     }
 
-    fn change_ratio(&mut self, delta: (f32, f32)) {
-        let scale = Self::map_rect_to_delta(self, delta);
-
-        self.ratio += scale;
-
-        if self.ratio < 0.05 {
-            self.ratio = 0.05;
+    ///
+    /// Same as `is_hovering_sizer` with the sizer metrics (in pixels) taken from the style. The
+    /// sizer also counts as hit within the hit padding on both sides of it. Returns the split
+    /// together with the index of the child the sizer follows.
+    ///
+    pub(crate) fn hovering_sizer(&self, pos: (f32, f32), style: &Style) -> Option<(SplitHandle, usize)> {
+        let padding = style.sizer_hit_padding;
+        let last = self.children.len() - 1;
+
+        for (index, child) in self.children[..last].iter().enumerate() {
+            let views = child.views.rect;
+            let rect = match self.direction {
+                Direction::Vertical => Rect::new(views.x, views.y, views.width - padding, views.height),
+                _ => Rect::new(views.x, views.y, views.width, views.height - padding),
+            };
+
+            if Split::is_hovering_rect(pos, style.sizer_size + padding * 2.0, rect, self.direction) {
+                return Some((self.handle, index));
+            }
         }
 
-        if self.ratio > 0.95 {
-            self.ratio = 0.95;
-        }
+        self.children.iter()
+            .filter_map(|child| child.split.as_ref())
+            .find_map(|split| split.hovering_sizer(pos, style))
     }
 
-    pub fn drag_sizer(&mut self, handle: SplitHandle, delta: (f32, f32)) {
-        if self.handle.0 == handle.0 {
-            return Self::change_ratio(self, delta);
-        }
-
-        if let Some(ref mut split) = self.left {
-            Self::drag_sizer(split, handle, delta); 
-        }
-
-        if let Some(ref mut split) = self.right {
-            Self::drag_sizer(split, handle, delta); 
+    /// Changes the ratio of the split with the handle. Returns false if it wasn't found
+    pub fn drag_sizer(&mut self, handle: SplitHandle, delta: (f32, f32)) -> bool {
+        match self.find_split_mut(handle) {
+            Some(split) => {
+                split.drag_child_sizer(0, delta, 0.0, None, &[], Style::default().min_panel_size);
+                true
+            }
+            None => false,
         }
     }
 }
 
-pub struct Workspace {
-    pub split: Option<Box<Split>>,
-    pub rect: Rect,
-    /// border size of the windows (in pixels)
-    pub window_border: f32,
-    handle_counter: SplitHandle,
+/// The docking tree. `T` is the type of user data each view owns
+pub struct Workspace<T = ()> {
+    /// The document area in the centre, see `dock_zone` for the areas around it
+    pub split: Option<Box<Split<T>>>,
+    /// Metrics for layout and hit-testing (in logical units)
+    pub style: Style,
+    /// Snapping of sizers in `drag_sizer`, disabled when None
+    pub snap: Option<SnapConfig>,
+    /// Round all boundaries in `update` to whole pixels so adjacent rects share their edges exactly
+    pub pixel_exact: bool,
+    /// Transitions of view rects in `update` and `update_animated`, disabled when None
+    pub animation: Option<Animation>,
+    /// Position and size of the workspace (in pixels), see `set_rect`
+    rect: Rect,
+    /// Physical pixels per logical unit, see `set_scale`
+    scale: f32,
+    /// Last split handle handed out, shared by all workspaces of a `WorkspaceManager`
+    handle_counter: Arc<AtomicU64>,
+    zones: DockZones<T>,
+    rules: Option<Box<dyn DockRules<T>>>,
+    layouts: Vec<(String, Snapshot)>,
+    /// Views that aren't part of the current layout, see `switch_layout`
+    hidden: Vec<View<T>>,
+    /// Undocked containers from the bottom to the top, see `float_view`
+    floating: Vec<FloatingWindow<T>>,
+    #[cfg(feature = "constraints")]
+    equal_sizes: Vec<EqualSizes>,
+    transitions: HashMap<ViewHandle, Transition>,
+    /// Scaled style and pixel snapping of the last `update`, None to lay out everything again
+    layout_key: Option<(Style, bool)>,
 }
 
-impl Workspace {
-    /// Construct a new workspace. The rect has to be y >= 0, x >= 0, width > 0 and height > 0
-    pub fn new(rect: Rect) -> Result<Workspace> {
+impl<T> Workspace<T> {
+    /// Construct a new workspace. The rect has to be finite with y >= 0, x >= 0, width > 0 and height > 0
+    pub fn new(rect: Rect) -> Result<Workspace<T>> {
+        Self::validate_rect(rect)?;
+
+        Ok(Workspace {
+            split: None,
+            style: Style::default(),
+            snap: None,
+            pixel_exact: false,
+            animation: None,
+            rect,
+            scale: 1.0,
+            handle_counter: Arc::new(AtomicU64::new(1)),
+            zones: dock::new_zones(),
+            rules: None,
+            layouts: Vec::new(),
+            hidden: Vec::new(),
+            floating: Vec::new(),
+            #[cfg(feature = "constraints")]
+            equal_sizes: Vec::new(),
+            transitions: HashMap::new(),
+            layout_key: None,
+        })
+    }
+
+    fn validate_rect(rect: Rect) -> Result<()> {
+        if !(rect.x.is_finite() && rect.y.is_finite() && rect.width.is_finite() && rect.height.is_finite()) {
+            return Err(Error::IllegalSize("the rect has to be finite".to_owned()));
+        }
+
         if rect.x < 0.0 {
             return Err(Error::IllegalSize("x has to be non-negative".to_owned()));
         }
@@ -342,128 +750,391 @@ impl Workspace {
             return Err(Error::IllegalSize("height has to be larger than 0.0".to_owned()));
         }
 
-        Ok(Workspace {
-            split: None,
-            rect: rect, 
-            window_border: 4.0,
-            handle_counter: SplitHandle(1),
-        })
+        Ok(())
     }
 
     /// This code gets called when the top split is None. This mean that the view will be
     /// set to fullscreen as there are no other splits to be done
-    fn split_new(&mut self, split_handle: SplitHandle, view_handle: ViewHandle) {
-        let mut split = Box::new(Split::new(Direction::Full, split_handle));
-        split.ratio = 1.0;
-        split.left_views.views.push(View::new(view_handle));
-        self.split = Some(split);
+    fn split_new(&mut self, split_handle: SplitHandle, view: View<T>) {
+        let mut container = Container::new();
+        container.views.push(view);
+        self.split = Some(Box::new(Split::full(split_handle, container)));
     }
 
-    pub fn split_top(&mut self, view_handle: ViewHandle, direction: Direction) {
-        self.handle_counter.0 += 1;
-        let split_handle = self.handle_counter;
-        if let Some(ref mut split) = self.split {
-            split.split_left(split_handle, view_handle, direction);
+    pub(crate) fn next_split_handle(&self) -> SplitHandle {
+        SplitHandle(self.handle_counter.fetch_add(1, Ordering::Relaxed) + 1)
+    }
+
+    /// Views can only be split vertically or horizontally, `Direction::Full` is reserved for the root
+    fn check_split_direction(direction: Direction) -> Result<()> {
+        match direction {
+            Direction::Full => Err(Error::ConstraintViolation("views can't be split with Direction::Full".to_owned())),
+            _ => Ok(()),
+        }
+    }
+
+    fn check_new_view(&self, handle: ViewHandle) -> Result<()> {
+        if self.has_view(handle) || self.is_hidden(handle) {
+            Err(Error::DuplicateHandle(handle))
         } else {
-            Self::split_new(self, split_handle, view_handle);
+            Ok(())
         }
     }
 
-    pub fn update(&mut self) {
-        let rect = self.rect.clone();
+    pub fn split_top(&mut self, view_handle: ViewHandle, direction: Direction) -> Result<()> where T: Default {
+        self.split_top_with(view_handle, direction, T::default())
+    }
+
+    /// Same as `split_top` with the user data owned by the new view
+    pub fn split_top_with(&mut self, view_handle: ViewHandle, direction: Direction, payload: T) -> Result<()> {
+        Self::check_split_direction(direction)?;
+        self.check_new_view(view_handle)?;
+
+        let view = View::new(view_handle, payload);
+        self.check_rules(&view, DropTarget::Area(DockArea::Center), DockArea::Center.side())?;
+
+        let split_handle = self.next_split_handle();
         if let Some(ref mut split) = self.split {
-            split.recursive_update(rect, 0);
+            split.split_left(split_handle, view, direction);
+        } else {
+            Self::split_new(self, split_handle, view);
         }
+
+        self.debug_validate();
+
+        Ok(())
     }
 
-    pub fn split_by_view_handle(&mut self, direction: Direction, find_handle: ViewHandle, handle: ViewHandle) {
-        if let Some(ref mut split) = self.split {
-            self.handle_counter.0 += 1;
-            split.split_by_view_handle(direction, self.handle_counter, find_handle, handle);
+    /// Physical pixels per logical unit. The workspace rect and all computed rects are in physical pixels
+    pub fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    /// Position and size of the workspace, change it with `set_rect`
+    pub fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    ///
+    /// Computes the rects of all containers and views and returns the views whose rect, tab or
+    /// close button moved. Only splits that changed since the last call are laid out again, see
+    /// `mark_dirty`. With `animation` set the views don't jump to their new rect but start moving
+    /// towards it, `update_animated` moves them along.
+    ///
+    pub fn update(&mut self) -> Vec<ViewHandle> {
+        self.update_animated(0.0)
+    }
+
+    /// Lays out the containers and views without animation, see `update`
+    pub(crate) fn lay_out(&mut self) -> Vec<ViewHandle> {
+        let pixel_exact = self.pixel_exact;
+        let scale = self.scale;
+        let style = self.metrics();
+        let mut rects = self.area_rects();
+
+        if pixel_exact {
+            rects.iter_mut().for_each(|rect| *rect = rect.round());
         }
+
+        #[cfg(feature = "constraints")]
+        self.solve_constraints(&rects);
+
+        let full = self.layout_key != Some((style, pixel_exact));
+        let mut changed = Vec::new();
+
+        for (split, &rect) in self.roots_mut().zip(rects.iter()) {
+            if let Some(ref mut split) = *split {
+                if full {
+                    split.recursive_update(rect, &style, scale, pixel_exact, &mut changed);
+                } else {
+                    split.incremental_update(rect, &style, scale, pixel_exact, &mut changed);
+                }
+            }
+        }
+
+        for window in &mut self.floating {
+            window.container.rect = if pixel_exact { window.rect.round() } else { window.rect };
+            window.container.update_header(&style, scale, pixel_exact, &mut changed);
+        }
+
+        self.layout_key = Some((style, pixel_exact));
+
+        changed
     }
 
-    pub fn is_hovering_sizer(&self, pos: (f32, f32)) -> Option<SplitHandle> {
-        if let Some(ref split) = self.split {
-            split.is_hovering_sizer(pos)
-        } else {
-            None
+    pub fn split_by_view_handle(&mut self, direction: Direction, find_handle: ViewHandle, handle: ViewHandle) -> Result<()>
+        where T: Default {
+        self.split_by_view_handle_with(direction, find_handle, handle, T::default())
+    }
+
+    /// Same as `split_by_view_handle` with the user data owned by the new view
+    pub fn split_by_view_handle_with(&mut self, direction: Direction, find_handle: ViewHandle, handle: ViewHandle,
+                                     payload: T) -> Result<()> {
+        Self::check_split_direction(direction)?;
+        self.check_new_view(handle)?;
+
+        let view = View::new(handle, payload);
+        self.check_rules(&view, DropTarget::View(find_handle), self.insert_side(direction, find_handle)?)?;
+
+        self.insert_view(direction, find_handle, view)
+    }
+
+    /// Docks the view next to the view with `find_handle`, which has to be dockable. The rules have
+    /// to be checked by the caller
+    fn insert_view(&mut self, direction: Direction, find_handle: ViewHandle, view: View<T>) -> Result<()> {
+        self.check_dockable(find_handle)?;
+
+        let area = self.area_of(find_handle).ok_or(Error::ViewNotFound(find_handle))?;
+
+        let split_handle = self.next_split_handle();
+
+        if let Some(ref mut split) = *self.tree_mut(area) {
+            split.split_by_view_handle(direction, split_handle, find_handle, view);
         }
+
+        self.debug_validate();
+
+        Ok(())
     }
 
-    pub fn drag_sizer(&mut self, handle: SplitHandle, delta: (f32, f32)) {
-        if let Some(ref mut split) = self.split {
-            split.drag_sizer(handle, delta)
+    fn has_view(&self, handle: ViewHandle) -> bool {
+        self.trees().any(|split| split.has_view(handle)) || self.is_floating(handle)
+    }
+
+    ///
+    /// Removes the view from the workspace. If the container of the view becomes empty the
+    /// split holding it is collapsed so the other side takes over its space. Fails if the view
+    /// isn't closable, otherwise the user data of the view is returned.
+    ///
+    pub fn remove_view(&mut self, handle: ViewHandle) -> Result<T> {
+        self.check_closable(handle)?;
+        let view = self.take_view(handle)?;
+
+        self.debug_validate();
+
+        Ok(view.into_payload())
+    }
+
+    /// Removes the view without looking at its flags
+    fn take_view(&mut self, handle: ViewHandle) -> Result<View<T>> {
+        let area = match self.area_of(handle) {
+            Some(area) => area,
+            None => return self.take_floating_view(handle).ok_or(Error::ViewNotFound(handle)),
+        };
+
+        let root = self.tree_mut(area);
+
+        let view = root.as_mut()
+            .and_then(|split| split.remove_view(handle))
+            .ok_or(Error::ViewNotFound(handle))?;
+
+        Split::collapse_root(root);
+
+        Ok(view)
+    }
+
+    ///
+    /// Moves a view next to the target view by removing it and splitting the target in the given
+    /// direction. The view has to be movable and the target dockable. The view keeps its title and
+    /// flags, user data and gets its preferred size if it has one.
+    ///
+    pub fn move_view(&mut self, direction: Direction, target: ViewHandle, handle: ViewHandle) -> Result<()> {
+        Self::check_split_direction(direction)?;
+        self.check_movable(handle)?;
+        self.check_dockable(target)?;
+
+        if handle == target {
+            return Err(Error::ConstraintViolation("a view can't be moved next to itself".to_owned()));
+        }
+
+        let side = self.insert_side(direction, target)?;
+        self.check_rules(self.view(handle).ok_or(Error::ViewNotFound(handle))?, DropTarget::View(target), side)?;
+
+        let view = self.take_view(handle)?;
+        let extent = self.container_extent(target, direction);
+
+        self.insert_view(direction, target, view)?;
+        self.apply_preferred_size(handle, extent);
+        self.debug_validate();
+
+        Ok(())
+    }
+
+    ///
+    /// Returns the split whose sizer is at the position. The sizer starts where the left/top panel
+    /// ends and is `Style::sizer_size` thick, `sizer_at` is the more forgiving hit test that also
+    /// counts `Style::sizer_hit_padding` around it.
+    ///
+    pub fn is_hovering_sizer(&self, pos: (f32, f32)) -> Option<SplitHandle> {
+        let style = Style { sizer_hit_padding: 0.0, ..self.metrics() };
+        self.trees().find_map(|split| split.hovering_sizer(pos, &style)).map(|(handle, _)| handle)
+    }
+
+    ///
+    /// Returns the split and the index of the child whose sizer is at the position, the sizer
+    /// between child `index` and `index + 1`. Pass both to `drag_sizer_n` to drag it. Positions
+    /// within `Style::sizer_hit_padding` of the sizer count as well.
+    ///
+    pub fn sizer_at(&self, pos: (f32, f32)) -> Option<(SplitHandle, usize)> {
+        let style = self.metrics();
+        self.trees().find_map(|split| split.hovering_sizer(pos, &style))
+    }
+
+    /// Drags the sizer after the first child of the split, see `drag_sizer_n`
+    pub fn drag_sizer(&mut self, handle: SplitHandle, delta: (f32, f32)) -> Result<()> {
+        self.drag_sizer_n(handle, 0, delta)
+    }
+
+    ///
+    /// Moves the sizer between child `index` and `index + 1` of the split by the pointer delta.
+    /// Only those two children change size, nested splits keep their ratios and scale with them.
+    /// Call `end_sizer_drag` when the pointer is released.
+    ///
+    pub fn drag_sizer_n(&mut self, handle: SplitHandle, index: usize, delta: (f32, f32)) -> Result<()> {
+        let leaves = match self.snap {
+            Some(ref snap) if snap.edges => self.leaf_rects(),
+            _ => Vec::new(),
+        };
+
+        let snap = self.snap.as_ref().map(|snap| SnapConfig { tolerance: snap.tolerance * self.scale, ..snap.clone() });
+        let min_size = self.metrics().min_panel_size;
+
+        let split = self.find_split_mut(handle).ok_or(Error::SplitNotFound(handle))?;
+
+        if index + 1 >= split.children.len() {
+            return Err(Error::ConstraintViolation(format!("split {} has no sizer {}", handle.0, index)));
         }
+
+        let offset = split.drag_offset;
+        split.drag_offset = split.drag_child_sizer(index, delta, offset, snap.as_ref(), &leaves, min_size);
+
+        self.debug_validate();
+
+        Ok(())
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod test {
-    use {Split, Workspace, Rect, ViewHandle, Direction};
-
-    fn check_range(inv: f32, value: f32, delta: f32) -> bool {
-        (inv - value).abs() < delta
-    }
+    use test_util::check_range;
+    use {Split, Workspace, Rect, ViewHandle, SplitHandle, Direction, Error};
 
     #[test]
     fn test_validate_x_less_than_zero() {
-        assert_eq!(Workspace::new(Rect::new(-0.1, 0.0, 1.0, 1.0)).is_err(), true);
+        assert_eq!(Workspace::<()>::new(Rect::new(-0.1, 0.0, 1.0, 1.0)).is_err(), true);
     }
 
     #[test]
     fn test_validate_y_less_than_zero() {
-        assert_eq!(Workspace::new(Rect::new(0.0, -0.1, 1.0, 1.0)).is_err(), true);
+        assert_eq!(Workspace::<()>::new(Rect::new(0.0, -0.1, 1.0, 1.0)).is_err(), true);
     }
 
     #[test]
     fn test_validate_width_zero() {
-        assert_eq!(Workspace::new(Rect::new(0.0, 0.0, 0.0, 1.0)).is_err(), true);
+        assert_eq!(Workspace::<()>::new(Rect::new(0.0, 0.0, 0.0, 1.0)).is_err(), true);
     }
 
     #[test]
     fn test_validate_height_zero() {
-        assert_eq!(Workspace::new(Rect::new(0.0, 0.0, 1.0, 0.0)).is_err(), true);
+        assert_eq!(Workspace::<()>::new(Rect::new(0.0, 0.0, 1.0, 0.0)).is_err(), true);
     }
 
     #[test]
     fn test_validate_width_less_than_zero() {
-        assert_eq!(Workspace::new(Rect::new(0.0, 0.0, -1.0, 0.0)).is_err(), true);
+        assert_eq!(Workspace::<()>::new(Rect::new(0.0, 0.0, -1.0, 0.0)).is_err(), true);
     }
 
     #[test]
     fn test_validate_height_less_than_zero() {
-        assert_eq!(Workspace::new(Rect::new(0.0, 0.0, 0.0, -1.0)).is_err(), true);
+        assert_eq!(Workspace::<()>::new(Rect::new(0.0, 0.0, 0.0, -1.0)).is_err(), true);
+    }
+
+    #[test]
+    fn test_validate_not_finite() {
+        assert_eq!(Workspace::<()>::new(Rect::new(0.0, 0.0, f32::NAN, 1.0)).is_err(), true);
+        assert_eq!(Workspace::<()>::new(Rect::new(f32::INFINITY, 0.0, 1.0, 1.0)).is_err(), true);
+        assert_eq!(Workspace::<()>::new(Rect::new(0.0, 0.0, 1.0, f32::INFINITY)).is_err(), true);
     }
 
     #[test]
     fn test_validate_workspace_ok() {
-        assert_eq!(Workspace::new(Rect::new(0.0, 0.0, 1024.0, 1024.0)).is_ok(), true);
+        assert_eq!(Workspace::<()>::new(Rect::new(0.0, 0.0, 1024.0, 1024.0)).is_ok(), true);
     }
 
     #[test]
     fn test_split_top() {
-        let mut ws = Workspace::new(Rect::new(0.0, 0.0, 1024.0, 1024.0)).unwrap();
-        ws.split_top(ViewHandle(1), Direction::Vertical);
+        let mut ws: Workspace = Workspace::new(Rect::new(0.0, 0.0, 1024.0, 1024.0)).unwrap();
+        ws.split_top(ViewHandle(1), Direction::Vertical).unwrap();
 
         assert_eq!(ws.split.is_some(), true);
         let split = ws.split.unwrap();
 
-        assert_eq!(split.left_views.views.len(), 1);
+        assert_eq!(split.left_views().views.len(), 1);
     }
 
     #[test]
     fn test_split_top_2() {
-        let mut ws = Workspace::new(Rect::new(0.0, 0.0, 1024.0, 1024.0)).unwrap();
-        ws.split_top(ViewHandle(1), Direction::Vertical);
-        ws.split_top(ViewHandle(2), Direction::Vertical);
+        let mut ws: Workspace = Workspace::new(Rect::new(0.0, 0.0, 1024.0, 1024.0)).unwrap();
+        ws.split_top(ViewHandle(1), Direction::Vertical).unwrap();
+        ws.split_top(ViewHandle(2), Direction::Vertical).unwrap();
 
         assert_eq!(ws.split.is_some(), true);
         let split = ws.split.unwrap();
 
-        assert_eq!(split.right_views.views.len(), 1);
-        assert_eq!(split.left_views.views.len(), 1);
-        assert_eq!(check_range(split.ratio, 0.5, 0.01), true);
+        assert_eq!(split.right_views().views.len(), 1);
+        assert_eq!(split.left_views().views.len(), 1);
+        assert_eq!(check_range(split.ratio(), 0.5, 0.01), true);
+    }
+
+    #[test]
+    fn test_split_duplicate_handle() {
+        let mut ws: Workspace = Workspace::new(Rect::new(0.0, 0.0, 1024.0, 1024.0)).unwrap();
+        ws.split_top(ViewHandle(1), Direction::Vertical).unwrap();
+
+        match ws.split_top(ViewHandle(1), Direction::Vertical) {
+            Err(Error::DuplicateHandle(ViewHandle(1))) => (),
+            res => panic!("unexpected result {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_split_by_missing_view() {
+        let mut ws: Workspace = Workspace::new(Rect::new(0.0, 0.0, 1024.0, 1024.0)).unwrap();
+        ws.split_top(ViewHandle(1), Direction::Vertical).unwrap();
+
+        match ws.split_by_view_handle(Direction::Vertical, ViewHandle(2), ViewHandle(3)) {
+            Err(Error::ViewNotFound(ViewHandle(2))) => (),
+            res => panic!("unexpected result {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_drag_missing_sizer() {
+        let mut ws: Workspace = Workspace::new(Rect::new(0.0, 0.0, 1024.0, 1024.0)).unwrap();
+        ws.split_top(ViewHandle(1), Direction::Vertical).unwrap();
+
+        match ws.drag_sizer(SplitHandle(42), (1.0, 0.0)) {
+            Err(Error::SplitNotFound(SplitHandle(42))) => (),
+            res => panic!("unexpected result {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_remove_view_collapses_split() {
+        let mut ws: Workspace = Workspace::new(Rect::new(0.0, 0.0, 1024.0, 1024.0)).unwrap();
+        ws.split_top(ViewHandle(1), Direction::Vertical).unwrap();
+        ws.split_top(ViewHandle(2), Direction::Vertical).unwrap();
+        ws.split_top(ViewHandle(3), Direction::Horizontal).unwrap();
+
+        ws.remove_view(ViewHandle(3)).unwrap();
+        ws.remove_view(ViewHandle(1)).unwrap();
+
+        let split = ws.split.as_ref().unwrap();
+        assert_eq!(split.left().is_none(), true);
+        assert_eq!(split.left_views().views[0].handle, ViewHandle(2));
+
+        assert_eq!(ws.remove_view(ViewHandle(1)).is_err(), true);
     }
 
     #[test]
@@ -544,4 +1215,17 @@ mod test {
         assert_eq!(Split::is_inside((9.0, 61.0), rect_horz), false);
         assert_eq!(Split::is_inside((11.0, 61.0), rect_horz), true);
     }
+
+    #[test]
+    fn test_hovering_sizer_scaled() {
+        let mut ws: Workspace = Workspace::new(Rect::new(0.0, 0.0, 1000.0, 1000.0)).unwrap();
+        ws.split_top(ViewHandle(1), Direction::Vertical).unwrap();
+        ws.split_top(ViewHandle(2), Direction::Vertical).unwrap();
+        ws.update();
+
+        assert_eq!(ws.is_hovering_sizer((512.0, 100.0)).is_none(), true);
+
+        ws.set_scale(2.0).unwrap();
+        assert_eq!(ws.is_hovering_sizer((512.0, 100.0)).is_some(), true);
+    }
 }
@@ -0,0 +1,277 @@
+use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use {DockArea, DropSide, DropTarget, Error, Rect, Result, ViewHandle, Workspace};
+
+/// Handle of a workspace owned by a `WorkspaceManager`, usually one per window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct WorkspaceHandle(pub u64);
+
+///
+/// Owns the workspaces of all windows of an application. Split handles are shared between the
+/// workspaces so they are unique across all of them and `new_view_handle` hands out view handles
+/// that aren't used by any workspace. Views can be moved between workspaces or torn off into a new
+/// one and the whole set can be saved and restored together, see `save`.
+///
+pub struct WorkspaceManager<T = ()> {
+    pub(crate) workspaces: Vec<(WorkspaceHandle, Workspace<T>)>,
+    pub(crate) handle_counter: Arc<AtomicU64>,
+    pub(crate) view_counter: u64,
+    pub(crate) workspace_counter: u64,
+}
+
+impl<T> Default for WorkspaceManager<T> {
+    fn default() -> WorkspaceManager<T> {
+        WorkspaceManager::new()
+    }
+}
+
+impl<T> WorkspaceManager<T> {
+    pub fn new() -> WorkspaceManager<T> {
+        WorkspaceManager {
+            workspaces: Vec::new(),
+            handle_counter: Arc::new(AtomicU64::new(1)),
+            view_counter: 0,
+            workspace_counter: 0,
+        }
+    }
+
+    /// Adds an empty workspace, see `Workspace::new` for the rules of the rect
+    pub fn add_workspace(&mut self, rect: Rect) -> Result<WorkspaceHandle> {
+        let ws = self.new_workspace(rect)?;
+        Ok(self.push_workspace(ws))
+    }
+
+    /// A workspace sharing the split handles of the manager that isn't added yet
+    fn new_workspace(&self, rect: Rect) -> Result<Workspace<T>> {
+        let mut ws = Workspace::new(rect)?;
+        ws.handle_counter = self.handle_counter.clone();
+        Ok(ws)
+    }
+
+    fn push_workspace(&mut self, ws: Workspace<T>) -> WorkspaceHandle {
+        self.workspace_counter += 1;
+        let handle = WorkspaceHandle(self.workspace_counter);
+        self.workspaces.push((handle, ws));
+        handle
+    }
+
+    /// Removes the workspace, for example when its window is closed, and returns it with all its views
+    pub fn remove_workspace(&mut self, handle: WorkspaceHandle) -> Result<Workspace<T>> {
+        let index = self.index_of(handle)?;
+        Ok(self.workspaces.remove(index).1)
+    }
+
+    fn index_of(&self, handle: WorkspaceHandle) -> Result<usize> {
+        self.workspaces.iter()
+            .position(|&(other, _)| other == handle)
+            .ok_or(Error::WorkspaceNotFound(handle))
+    }
+
+    pub fn workspace(&self, handle: WorkspaceHandle) -> Option<&Workspace<T>> {
+        self.workspaces.iter().find(|&&(other, _)| other == handle).map(|(_, ws)| ws)
+    }
+
+    pub fn workspace_mut(&mut self, handle: WorkspaceHandle) -> Option<&mut Workspace<T>> {
+        self.workspaces.iter_mut().find(|&&mut (other, _)| other == handle).map(|(_, ws)| ws)
+    }
+
+    /// Iterates over all workspaces in the order they were added
+    pub fn workspaces(&self) -> impl Iterator<Item = (WorkspaceHandle, &Workspace<T>)> {
+        self.workspaces.iter().map(|(handle, ws)| (*handle, ws))
+    }
+
+    /// Returns a view handle that isn't used by any of the workspaces
+    pub fn new_view_handle(&mut self) -> ViewHandle {
+        loop {
+            self.view_counter += 1;
+            let handle = ViewHandle(self.view_counter);
+
+            if self.find_view(handle).is_none() {
+                return handle;
+            }
+        }
+    }
+
+    /// Returns the workspace holding the view, also if it's hidden by `Workspace::switch_layout`
+    pub fn find_view(&self, handle: ViewHandle) -> Option<WorkspaceHandle> {
+        self.workspaces.iter()
+            .find(|(_, ws)| ws.has_view(handle) || ws.is_hidden(handle))
+            .map(|&(other, _)| other)
+    }
+
+    ///
+    /// Moves the view into another workspace, next to `target` or into one of its areas. The view
+    /// has to be movable and the destination has to accept it by its own rules. Moving within the
+    /// same workspace behaves like `Workspace::move_to_side` and `Workspace::move_to_dock`.
+    ///
+    pub fn move_view(&mut self, handle: ViewHandle, to: WorkspaceHandle, target: DropTarget, side: DropSide) -> Result<()> {
+        let from = self.find_view(handle).ok_or(Error::ViewNotFound(handle))?;
+        let to_index = self.index_of(to)?;
+        let from_index = self.index_of(from)?;
+
+        if from_index == to_index {
+            let ws = &mut self.workspaces[to_index].1;
+
+            return match target {
+                DropTarget::Area(area) => ws.move_to_dock(area, handle),
+                DropTarget::View(target) => ws.move_to_side(target, handle, side),
+            };
+        }
+
+        {
+            let source = &self.workspaces[from_index].1;
+            let dest = &self.workspaces[to_index].1;
+
+            source.check_movable(handle)?;
+            let view = source.view(handle).ok_or(Error::ViewNotFound(handle))?;
+
+            let side = match target {
+                DropTarget::View(target) => {
+                    dest.check_dockable(target)?;
+                    side
+                }
+                DropTarget::Area(area) => area.side(),
+            };
+
+            dest.check_rules(view, target, side)?;
+        }
+
+        let view = self.workspaces[from_index].1.take_view(handle)?;
+        let dest = &mut self.workspaces[to_index].1;
+
+        match target {
+            DropTarget::Area(area) => dest.dock(area, view)?,
+            DropTarget::View(target) => dest.place_view(view, target, side)?,
+        }
+
+        dest.debug_validate();
+
+        Ok(())
+    }
+
+    ///
+    /// Moves the view out of its workspace into a new one with the rect, for example for a new
+    /// window. The view fills the document area of the new workspace, which gets the style, snapping
+    /// and scale of the old one.
+    ///
+    pub fn tear_off(&mut self, handle: ViewHandle, rect: Rect) -> Result<WorkspaceHandle> {
+        let from = self.find_view(handle).ok_or(Error::ViewNotFound(handle))?;
+        let from_index = self.index_of(from)?;
+
+        // The new workspace is only added once it holds the view, so a failure leaves everything as it was
+        let mut dest = self.new_workspace(rect)?;
+
+        {
+            let source = &self.workspaces[from_index].1;
+            source.check_movable(handle)?;
+
+            dest.style = source.style;
+            dest.snap = source.snap.clone();
+            dest.pixel_exact = source.pixel_exact;
+            dest.scale = source.scale();
+
+            let view = source.view(handle).ok_or(Error::ViewNotFound(handle))?;
+            dest.check_rules(view, DropTarget::Area(DockArea::Center), DockArea::Center.side())?;
+        }
+
+        let view = self.workspaces[from_index].1.take_view(handle)?;
+        dest.dock(DockArea::Center, view)?;
+        dest.update();
+
+        Ok(self.push_workspace(dest))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod test {
+    use {Direction, DockArea, DropSide, DropTarget, Error, Rect, ViewHandle, WorkspaceManager};
+
+    fn manager() -> WorkspaceManager {
+        let mut manager: WorkspaceManager = WorkspaceManager::new();
+
+        for _ in 0..2 {
+            let handle = manager.add_workspace(Rect::new(0.0, 0.0, 800.0, 600.0)).unwrap();
+            let first = manager.new_view_handle();
+            let second = manager.new_view_handle();
+
+            let ws = manager.workspace_mut(handle).unwrap();
+            ws.split_top(first, Direction::Vertical).unwrap();
+            ws.split_by_view_handle(Direction::Vertical, first, second).unwrap();
+        }
+
+        manager
+    }
+
+    #[test]
+    fn test_unique_handles() {
+        let manager = manager();
+        let mut views = Vec::new();
+        let mut splits = Vec::new();
+
+        for (_, ws) in manager.workspaces() {
+            views.extend(ws.views().map(|(view, _)| view.handle));
+            splits.push(ws.split.as_ref().unwrap().handle);
+        }
+
+        views.sort_by_key(|handle| handle.0);
+        views.dedup();
+        splits.dedup();
+
+        assert_eq!(views.len(), 4);
+        assert_eq!(splits.len(), 2);
+    }
+
+    #[test]
+    fn test_move_between_workspaces() {
+        let mut manager = manager();
+        let handles: Vec<_> = manager.workspaces().map(|(handle, _)| handle).collect();
+
+        manager.move_view(ViewHandle(1), handles[1], DropTarget::View(ViewHandle(3)), DropSide::Bottom).unwrap();
+
+        assert_eq!(manager.find_view(ViewHandle(1)), Some(handles[1]));
+        assert_eq!(manager.workspace(handles[0]).unwrap().views().count(), 1);
+        assert_eq!(manager.workspace(handles[1]).unwrap().validate().is_ok(), true);
+
+        manager.move_view(ViewHandle(2), handles[1], DropTarget::Area(DockArea::Left), DropSide::Left).unwrap();
+        assert_eq!(manager.workspace(handles[0]).unwrap().split.is_none(), true);
+        assert_eq!(manager.workspace(handles[1]).unwrap().area_of(ViewHandle(2)), Some(DockArea::Left));
+
+        match manager.move_view(ViewHandle(9), handles[0], DropTarget::Area(DockArea::Center), DropSide::Tab) {
+            Err(Error::ViewNotFound(ViewHandle(9))) => (),
+            _ => panic!("expected ViewNotFound"),
+        }
+    }
+
+    #[test]
+    fn test_tear_off() {
+        let mut manager = manager();
+        let first = manager.find_view(ViewHandle(1)).unwrap();
+
+        manager.workspace_mut(first).unwrap().set_scale(2.0).unwrap();
+
+        let torn = manager.tear_off(ViewHandle(2), Rect::new(0.0, 0.0, 400.0, 300.0)).unwrap();
+        let ws = manager.workspace(torn).unwrap();
+
+        assert_eq!(ws.scale(), 2.0);
+        assert_eq!(ws.view(ViewHandle(2)).unwrap().rect.width, 400.0);
+        assert_eq!(manager.find_view(ViewHandle(2)), Some(torn));
+        assert_eq!(manager.new_view_handle(), ViewHandle(5));
+    }
+
+    #[test]
+    fn test_failed_tear_off_keeps_view() {
+        let mut manager = manager();
+        let first = manager.find_view(ViewHandle(1)).unwrap();
+
+        manager.workspace_mut(first).unwrap().view_mut(ViewHandle(1)).unwrap().flags.movable = false;
+
+        assert_eq!(manager.tear_off(ViewHandle(1), Rect::new(0.0, 0.0, 400.0, 300.0)).is_err(), true);
+        assert_eq!(manager.tear_off(ViewHandle(2), Rect::new(0.0, 0.0, 0.0, 300.0)).is_err(), true);
+
+        // No empty workspace is left behind and the views stay where they were
+        assert_eq!(manager.workspaces().count(), 2);
+        assert_eq!(manager.find_view(ViewHandle(1)), Some(first));
+        assert_eq!(manager.find_view(ViewHandle(2)), Some(first));
+    }
+}
@@ -0,0 +1,298 @@
+use {Container, Direction, Error, Result, Split, View, ViewHandle, Workspace};
+
+/// What the user is allowed to do with a view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ViewFlags {
+    /// The view can be removed with `Workspace::remove_view`
+    pub closable: bool,
+    /// The view can be moved to another place with `Workspace::move_view`
+    pub movable: bool,
+    /// Other views can be docked next to this view
+    pub dockable: bool,
+}
+
+impl Default for ViewFlags {
+    fn default() -> ViewFlags {
+        ViewFlags {
+            closable: true,
+            movable: true,
+            dockable: true,
+        }
+    }
+}
+
+impl<T> Split<T> {
+    pub(crate) fn find_container(&self, handle: ViewHandle) -> Option<&Container<T>> {
+        for child in &self.children {
+            if child.views.views.iter().any(|view| view.handle == handle) {
+                return Some(&child.views);
+            }
+
+            if let Some(container) = child.split.as_ref().and_then(|split| split.find_container(handle)) {
+                return Some(container);
+            }
+        }
+
+        None
+    }
+
+    fn find_view(&self, handle: ViewHandle) -> Option<&View<T>> {
+        self.find_container(handle)
+            .and_then(|container| container.views.iter().find(|view| view.handle == handle))
+    }
+
+    pub(crate) fn for_each_view_mut(&mut self, f: &mut dyn FnMut(&mut View<T>)) {
+        for child in &mut self.children {
+            if let Some(ref mut split) = child.split {
+                split.for_each_view_mut(f);
+            }
+
+            child.views.views.iter_mut().for_each(&mut *f);
+        }
+    }
+
+    fn collect_views<'a>(&'a self, out: &mut Vec<&'a View<T>>) {
+        for child in &self.children {
+            if let Some(ref split) = child.split {
+                split.collect_views(out);
+            }
+
+            out.extend(child.views.views.iter());
+        }
+    }
+
+    /// Finds the split that holds the view in one of its containers, the splits on the way are marked dirty
+    fn find_parent_mut(&mut self, handle: ViewHandle) -> Option<&mut Split<T>> {
+        if !self.has_view(handle) {
+            return None;
+        }
+
+        self.dirty = true;
+
+        if self.children.iter().any(|child| child.views.views.iter().any(|view| view.handle == handle)) {
+            return Some(self);
+        }
+
+        self.children.iter_mut()
+            .filter_map(|child| child.split.as_mut())
+            .find(|split| split.has_view(handle))
+            .and_then(|split| split.find_parent_mut(handle))
+    }
+
+    fn find_view_mut(&mut self, handle: ViewHandle) -> Option<&mut View<T>> {
+        self.child_with_view_mut(handle)
+            .and_then(|child| child.views.views.iter_mut().find(|view| view.handle == handle))
+    }
+}
+
+impl<T> Workspace<T> {
+    /// Returns the view with the handle
+    pub fn view(&self, handle: ViewHandle) -> Option<&View<T>> {
+        self.trees().filter_map(|split| split.find_view(handle)).next()
+            .or_else(|| self.find_floating_view(handle))
+    }
+
+    /// Returns the view with the handle to change its title, flags or preferred size
+    pub fn view_mut(&mut self, handle: ViewHandle) -> Option<&mut View<T>> {
+        if self.is_floating(handle) {
+            return self.find_floating_view_mut(handle);
+        }
+
+        self.trees_mut().filter_map(|split| split.find_view_mut(handle)).next()
+    }
+
+    ///
+    /// Iterates over all views together with their user data, in layout order starting with the
+    /// document area and ending with the floating windows.
+    ///
+    pub fn views(&self) -> impl Iterator<Item = (&View<T>, &T)> {
+        let mut views = Vec::new();
+
+        for split in self.trees() {
+            split.collect_views(&mut views);
+        }
+
+        views.extend(self.floating.iter().flat_map(|window| window.container.views.iter()));
+
+        views.into_iter().map(|view| (view, view.payload()))
+    }
+
+    /// Calls `f` for every docked and floating view
+    pub(crate) fn for_each_view_mut(&mut self, f: &mut dyn FnMut(&mut View<T>)) {
+        for split in self.trees_mut() {
+            split.for_each_view_mut(f);
+        }
+
+        for window in &mut self.floating {
+            window.container.views.iter_mut().for_each(&mut *f);
+        }
+    }
+
+    /// Returns the user data of the view
+    pub fn payload(&self, handle: ViewHandle) -> Option<&T> {
+        self.view(handle).map(|view| view.payload())
+    }
+
+    pub fn payload_mut(&mut self, handle: ViewHandle) -> Option<&mut T> {
+        self.view_mut(handle).map(|view| view.payload_mut())
+    }
+
+    fn check_flag(&self, handle: ViewHandle, flag: fn(&ViewFlags) -> bool, what: &str) -> Result<()> {
+        match self.view(handle) {
+            Some(view) if flag(&view.flags) => Ok(()),
+            Some(_) => Err(Error::ConstraintViolation(format!("view {} is not {}", handle.0, what))),
+            None => Err(Error::ViewNotFound(handle)),
+        }
+    }
+
+    pub(crate) fn check_closable(&self, handle: ViewHandle) -> Result<()> {
+        self.check_flag(handle, |flags| flags.closable, "closable")
+    }
+
+    pub(crate) fn check_movable(&self, handle: ViewHandle) -> Result<()> {
+        self.check_flag(handle, |flags| flags.movable, "movable")
+    }
+
+    pub(crate) fn check_dockable(&self, handle: ViewHandle) -> Result<()> {
+        self.check_flag(handle, |flags| flags.dockable, "dockable")
+    }
+
+    ///
+    /// Gives the view its preferred size along the direction of the split holding it, if it has one
+    /// and the split has `extent` pixels. The size is clamped so both sides stay at least
+    /// `Style::min_panel_size` large.
+    ///
+    pub(crate) fn apply_preferred_size(&mut self, handle: ViewHandle, extent: f32) {
+        let preferred = match self.view(handle).and_then(|view| view.preferred_size) {
+            Some(size) => size,
+            None => return,
+        };
+
+        let min_size = self.metrics().min_panel_size;
+        let scale = self.scale;
+
+        let split = match self.trees_mut().filter_map(|split| split.find_parent_mut(handle)).next() {
+            Some(split) => split,
+            None => return,
+        };
+
+        let size = match split.direction {
+            Direction::Vertical => preferred.0,
+            Direction::Horizontal => preferred.1,
+            Direction::Full => return,
+        } * scale;
+
+        if extent <= min_size * 2.0 || size <= 0.0 {
+            return;
+        }
+
+        let share = size.clamp(min_size, extent - min_size) / extent;
+
+        if let Some(index) = split.children.iter().position(|child| child.views.views.iter().any(|view| view.handle == handle)) {
+            split.set_share(index, share);
+        }
+    }
+
+    /// Extent of the container holding the view along the direction, from the last `update`
+    pub(crate) fn container_extent(&self, handle: ViewHandle, direction: Direction) -> f32 {
+        let rect = match self.trees().filter_map(|split| split.find_container(handle)).next() {
+            Some(container) => container.rect,
+            None => return 0.0,
+        };
+
+        match direction {
+            Direction::Vertical => rect.width,
+            Direction::Horizontal => rect.height,
+            Direction::Full => 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod test {
+    use test_util::check_range;
+    use {Direction, Error, Rect, ViewHandle, Workspace};
+
+    fn workspace() -> Workspace {
+        let mut ws: Workspace = Workspace::new(Rect::new(0.0, 0.0, 1000.0, 1000.0)).unwrap();
+        ws.split_top(ViewHandle(1), Direction::Vertical).unwrap();
+        ws.split_top(ViewHandle(2), Direction::Vertical).unwrap();
+        ws.split_by_view_handle(Direction::Horizontal, ViewHandle(2), ViewHandle(3)).unwrap();
+        ws.update();
+        ws
+    }
+
+    #[test]
+    fn test_view_metadata() {
+        let mut ws = workspace();
+
+        ws.view_mut(ViewHandle(1)).unwrap().title = "Editor".to_owned();
+
+        assert_eq!(ws.view(ViewHandle(1)).unwrap().title, "Editor");
+        assert_eq!(ws.view(ViewHandle(2)).unwrap().flags.closable, true);
+        assert_eq!(ws.view(ViewHandle(4)).is_none(), true);
+    }
+
+    #[test]
+    fn test_flags_are_respected() {
+        let mut ws = workspace();
+
+        {
+            let flags = &mut ws.view_mut(ViewHandle(1)).unwrap().flags;
+            flags.closable = false;
+            flags.movable = false;
+            flags.dockable = false;
+        }
+
+        match ws.remove_view(ViewHandle(1)) {
+            Err(Error::ConstraintViolation(_)) => (),
+            _ => panic!("expected non-closable view to stay"),
+        }
+
+        assert_eq!(ws.move_view(Direction::Vertical, ViewHandle(2), ViewHandle(1)).is_err(), true);
+        assert_eq!(ws.move_view(Direction::Vertical, ViewHandle(1), ViewHandle(3)).is_err(), true);
+        assert_eq!(ws.split_by_view_handle(Direction::Vertical, ViewHandle(1), ViewHandle(4)).is_err(), true);
+        assert_eq!(ws.view(ViewHandle(1)).is_some(), true);
+    }
+
+    #[test]
+    fn test_move_keeps_metadata_and_preferred_size() {
+        let mut ws = workspace();
+
+        {
+            let view = ws.view_mut(ViewHandle(3)).unwrap();
+            view.title = "Output".to_owned();
+            view.flags.closable = false;
+            view.preferred_size = Some((200.0, 100.0));
+        }
+
+        ws.move_view(Direction::Vertical, ViewHandle(1), ViewHandle(3)).unwrap();
+        ws.update();
+
+        let view = ws.view(ViewHandle(3)).unwrap();
+        assert_eq!(view.title, "Output");
+        assert_eq!(view.flags.closable, false);
+        assert_eq!(check_range(view.rect.width, 200.0, 0.01), true);
+    }
+
+    #[test]
+    fn test_payload() {
+        let mut ws: Workspace<String> = Workspace::new(Rect::new(0.0, 0.0, 1000.0, 1000.0)).unwrap();
+        ws.split_top_with(ViewHandle(1), Direction::Vertical, "explorer".to_owned()).unwrap();
+        ws.split_by_view_handle_with(Direction::Vertical, ViewHandle(1), ViewHandle(2), "editor".to_owned()).unwrap();
+        ws.split_by_view_handle_with(Direction::Horizontal, ViewHandle(2), ViewHandle(3), "output".to_owned()).unwrap();
+
+        ws.payload_mut(ViewHandle(2)).unwrap().push_str(" 1");
+        ws.move_view(Direction::Vertical, ViewHandle(1), ViewHandle(3)).unwrap();
+
+        let views = ws.views().map(|(view, payload)| (view.handle.0, payload.clone())).collect::<Vec<_>>();
+        assert_eq!(views.len(), 3);
+        assert_eq!(views.contains(&(2, "editor 1".to_owned())), true);
+        assert_eq!(ws.payload(ViewHandle(3)).unwrap(), "output");
+
+        assert_eq!(ws.remove_view(ViewHandle(3)).unwrap(), "output");
+        assert_eq!(ws.payload(ViewHandle(3)).is_none(), true);
+        assert_eq!(ws.views().count(), 2);
+    }
+}
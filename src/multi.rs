@@ -0,0 +1,298 @@
+use std::mem;
+use snap::edges_outside;
+use {Direction, DropSide, DropTarget, Error, Rect, Result, SnapConfig, Split, SplitChild, SplitHandle, View, ViewHandle,
+     Workspace, MIN_RATIO};
+
+impl<T> Split<T> {
+    fn extent(&self) -> f32 {
+        match self.direction {
+            Direction::Vertical => self.rect.width,
+            Direction::Horizontal => self.rect.height,
+            Direction::Full => 0.0,
+        }
+    }
+
+    /// Sets the weights of the children from sizes that are relative to each other
+    pub(crate) fn set_weights(&mut self, sizes: &[f32]) {
+        let total = sizes.iter().sum::<f32>();
+
+        if total <= 0.0 {
+            return;
+        }
+
+        for (child, size) in self.children.iter_mut().zip(sizes) {
+            child.weight = (size / total).clamp(MIN_RATIO, 1.0 - MIN_RATIO);
+        }
+
+        self.dirty = true;
+    }
+
+    ///
+    /// Moves the sizer between child `index` and `index + 1`. Only those two children change size.
+    /// With snapping the sizer locks to snap points and the returned offset between pointer and
+    /// sizer has to be passed in on the next drag. Both children are kept at least `min_size`
+    /// large if they fit.
+    ///
+    pub(crate) fn drag_child_sizer(&mut self, index: usize, delta: (f32, f32), offset: f32,
+                                   snap: Option<&SnapConfig>, leaves: &[Rect], min_size: f32) -> f32 {
+        let extent = self.extent();
+
+        if extent <= 0.0 || index + 1 >= self.children.len() {
+            return 0.0;
+        }
+
+        let (origin, delta) = match self.direction {
+            Direction::Vertical => (self.rect.x, delta.0),
+            _ => (self.rect.y, delta.1),
+        };
+
+        let weights: Vec<f32> = self.children.iter().map(|child| child.weight).collect();
+
+        let start = origin + extent * weights[..index].iter().sum::<f32>();
+        let pair = extent * (weights[index] + weights[index + 1]);
+        let min = min_size.clamp(pair * 0.01, pair * 0.5);
+        let pointer = (extent * weights[index] - delta + offset).clamp(min, pair - min);
+
+        let first = match snap {
+            Some(snap) => {
+                let pair_rect = match self.direction {
+                    Direction::Vertical => Rect::new(start, self.rect.y, pair, self.rect.height),
+                    _ => Rect::new(self.rect.x, start, self.rect.width, pair),
+                };
+
+                let edges = if snap.edges {
+                    edges_outside(leaves, pair_rect, self.direction)
+                } else {
+                    Vec::new()
+                };
+
+                (snap.snap(start + pointer, start, pair, &edges) - start).clamp(min, pair - min)
+            }
+            None => pointer,
+        };
+
+        self.dirty = true;
+        self.children[index].weight = (first / extent).clamp(MIN_RATIO, 1.0 - MIN_RATIO);
+        self.children[index + 1].weight = ((pair - first) / extent).clamp(MIN_RATIO, 1.0 - MIN_RATIO);
+
+        pointer - first
+    }
+
+    pub(crate) fn has_split(&self, handle: SplitHandle) -> bool {
+        self.handle == handle || self.children.iter().any(|child| child.split.as_ref().is_some_and(|split| split.has_split(handle)))
+    }
+
+    /// Returns the child whose container holds the view, the splits on the way are marked dirty
+    pub(crate) fn child_with_view_mut(&mut self, handle: ViewHandle) -> Option<&mut SplitChild<T>> {
+        let index = self.children.iter().position(|child| child.has_view(handle))?;
+        self.dirty = true;
+
+        let child = &mut self.children[index];
+
+        if child.views.views.iter().any(|view| view.handle == handle) {
+            return Some(child);
+        }
+
+        child.split.as_mut().and_then(|split| split.child_with_view_mut(handle))
+    }
+}
+
+impl<T> Workspace<T> {
+    ///
+    /// Splits the container holding `find_handle` into a split with one child for the container
+    /// followed by one child for each of the new views. All of them get the same amount of space
+    /// and dragging the sizer between two of them only resizes those two, see `drag_sizer_n`.
+    ///
+    pub fn split_by_view_handle_n(&mut self, direction: Direction, find_handle: ViewHandle, handles: &[ViewHandle]) -> Result<()>
+        where T: Default {
+        Self::check_split_direction(direction)?;
+
+        for (index, &handle) in handles.iter().enumerate() {
+            if handles[..index].contains(&handle) {
+                return Err(Error::DuplicateHandle(handle));
+            }
+
+            self.check_new_view(handle)?;
+        }
+
+        self.check_dockable(find_handle)?;
+
+        if handles.is_empty() {
+            return Ok(());
+        }
+
+        let views: Vec<View<T>> = handles.iter().map(|&handle| View::new(handle, T::default())).collect();
+        let side = DropSide::from_direction(direction, false);
+
+        for view in &views {
+            self.check_rules(view, DropTarget::View(find_handle), side)?;
+        }
+
+        let area = self.area_of(find_handle).ok_or(Error::ViewNotFound(find_handle))?;
+        let split_handle = self.next_split_handle();
+
+        let tree = self.tree_mut(area);
+        let root = tree.as_mut().ok_or(Error::ViewNotFound(find_handle))?;
+        let is_full = root.direction == Direction::Full;
+
+        {
+            let child = root.child_with_view_mut(find_handle).ok_or(Error::ViewNotFound(find_handle))?;
+
+            let mut split = Box::new(Split::with_children(direction, split_handle, views.len() + 1));
+            split.children[0].views = mem::take(&mut child.views);
+
+            for (new, view) in split.children[1..].iter_mut().zip(views) {
+                new.views.views.push(view);
+            }
+
+            child.split = Some(split);
+        }
+
+        // A full root holds a single container so the new split replaces it
+        if is_full {
+            *tree = root.left_mut().take();
+        }
+
+        self.debug_validate();
+
+        Ok(())
+    }
+
+    ///
+    /// Sets the weights of the children of the split. Weights are relative to each other so
+    /// `[1.0, 1.0, 1.0]` gives three equally sized children.
+    ///
+    pub fn set_split_weights(&mut self, handle: SplitHandle, weights: &[f32]) -> Result<()> {
+        let split = self.find_split_mut(handle).ok_or(Error::SplitNotFound(handle))?;
+
+        if split.direction == Direction::Full {
+            return Err(Error::ConstraintViolation("a Direction::Full split has no sizer".to_owned()));
+        }
+
+        if weights.len() != split.children.len() {
+            return Err(Error::ConstraintViolation(format!("expected {} weights but got {}", split.children.len(), weights.len())));
+        }
+
+        if weights.iter().any(|weight| !(weight.is_finite() && *weight > 0.0)) {
+            return Err(Error::ConstraintViolation("weights have to be larger than 0.0".to_owned()));
+        }
+
+        split.set_weights(weights);
+
+        self.debug_validate();
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod test {
+    use test_util::check_range;
+    use {view, Direction, Layout, Rect, SplitHandle, ViewHandle, Workspace};
+
+    fn three_columns() -> Workspace {
+        let mut ws: Workspace = Workspace::new(Rect::new(0.0, 0.0, 900.0, 600.0)).unwrap();
+        ws.split_top(ViewHandle(1), Direction::Vertical).unwrap();
+        ws.split_by_view_handle_n(Direction::Vertical, ViewHandle(1), &[ViewHandle(2), ViewHandle(3)]).unwrap();
+        ws.update();
+        ws
+    }
+
+    fn column_widths(ws: &Workspace) -> Vec<f32> {
+        (1..4).map(|handle| ws.view(ViewHandle(handle)).unwrap().rect.width).collect()
+    }
+
+    #[test]
+    fn test_split_n_equal_columns() {
+        let ws = three_columns();
+        let split = ws.split.as_ref().unwrap();
+
+        assert_eq!(split.children.len(), 3);
+        assert_eq!(split.children[2].views.views[0].handle, ViewHandle(3));
+
+        for child in &split.children {
+            assert_eq!(check_range(child.weight, 1.0 / 3.0, 0.001), true);
+        }
+
+        assert_eq!(ws.validate().is_ok(), true);
+    }
+
+    #[test]
+    fn test_drag_middle_sizer_moves_neighbours_only() {
+        let mut ws = three_columns();
+        let root = ws.split.as_ref().unwrap().handle;
+
+        // Dragging the sizer between the first and second column keeps the third in place
+        assert_eq!(ws.sizer_at((301.0, 100.0)), Some((root, 0)));
+        ws.drag_sizer_n(root, 0, (-50.0, 0.0)).unwrap();
+        ws.update();
+
+        let widths = column_widths(&ws);
+        assert_eq!(check_range(widths[0], 350.0, 0.01), true);
+        assert_eq!(check_range(widths[1], 250.0, 0.01), true);
+        assert_eq!(check_range(widths[2], 300.0, 0.01), true);
+
+        assert_eq!(ws.sizer_at((601.0, 100.0)), Some((root, 1)));
+        ws.drag_sizer_n(root, 1, (50.0, 0.0)).unwrap();
+        ws.update();
+
+        let widths = column_widths(&ws);
+        assert_eq!(check_range(widths[0], 350.0, 0.01), true);
+        assert_eq!(check_range(widths[1], 200.0, 0.01), true);
+        assert_eq!(check_range(widths[2], 350.0, 0.01), true);
+
+        assert_eq!(ws.drag_sizer_n(root, 2, (50.0, 0.0)).is_err(), true);
+    }
+
+    #[test]
+    fn test_remove_child() {
+        let mut ws = three_columns();
+        let root = ws.split.as_ref().unwrap().handle;
+
+        ws.set_split_weights(root, &[1.0, 2.0, 1.0]).unwrap();
+        ws.remove_view(ViewHandle(2)).unwrap();
+        ws.update();
+
+        // The space of the removed column is shared by the others in proportion
+        let split = ws.split.as_ref().unwrap();
+        assert_eq!(split.children.len(), 2);
+        assert_eq!(check_range(ws.view(ViewHandle(3)).unwrap().rect.width, 450.0, 0.01), true);
+
+        ws.remove_view(ViewHandle(3)).unwrap();
+        assert_eq!(ws.split.as_ref().unwrap().direction, Direction::Full);
+        assert_eq!(ws.validate().is_ok(), true);
+    }
+
+    #[test]
+    fn test_set_split_weights() {
+        let mut ws = three_columns();
+        let root = ws.split.as_ref().unwrap().handle;
+
+        ws.set_split_weights(root, &[1.0, 2.0, 1.0]).unwrap();
+        ws.update();
+
+        let widths = column_widths(&ws);
+        assert_eq!(check_range(widths[0], 225.0, 0.01), true);
+        assert_eq!(check_range(widths[1], 450.0, 0.01), true);
+        assert_eq!(check_range(widths[2], 225.0, 0.01), true);
+
+        assert_eq!(ws.set_split_weights(root, &[1.0, 2.0]).is_err(), true);
+        assert_eq!(ws.set_split_weights(SplitHandle(1000), &[1.0, 2.0, 1.0]).is_err(), true);
+    }
+
+    #[test]
+    fn test_binary_drag_scales_nested_splits() {
+        // A | (B | C), dragging the outer sizer resizes B and C together
+        let layout = Layout::vertical(0.5).left(view(1)).right(Layout::vertical(0.5).left(view(2)).right(view(3)));
+        let mut ws: Workspace = Workspace::from_layout(Rect::new(0.0, 0.0, 1000.0, 1000.0), layout).unwrap();
+
+        let root = ws.split.as_ref().unwrap().handle;
+        ws.drag_sizer(root, (-100.0, 0.0)).unwrap();
+        ws.update();
+
+        assert_eq!(check_range(ws.split.as_ref().unwrap().ratio(), 0.6, 0.001), true);
+        assert_eq!(check_range(ws.view(ViewHandle(2)).unwrap().rect.width, 200.0, 0.01), true);
+        assert_eq!(check_range(ws.view(ViewHandle(3)).unwrap().rect.width, 200.0, 0.01), true);
+    }
+}
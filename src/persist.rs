@@ -0,0 +1,998 @@
+use std::fmt::Write;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use float::check_window_rect;
+use grid::check_tracks;
+use snapshot::{Skeleton, SkeletonSide, Snapshot};
+use {Container, FloatingWindow, Direction, DockArea, Error, Grid, GridCell, Rect, Result, Split, SplitChild, SplitHandle, View, ViewFlags, ViewHandle, ViewKind,
+     Track, Workspace, WorkspaceHandle, WorkspaceManager};
+
+/// First line of a saved manager, bumped when the format changes
+const HEADER: &str = "viewdock 2";
+
+fn area_name(area: DockArea) -> &'static str {
+    match area {
+        DockArea::Center => "center",
+        DockArea::Left => "left",
+        DockArea::Right => "right",
+        DockArea::Top => "top",
+        DockArea::Bottom => "bottom",
+    }
+}
+
+fn direction_name(direction: Direction) -> &'static str {
+    match direction {
+        Direction::Vertical => "vertical",
+        Direction::Horizontal => "horizontal",
+        Direction::Full => "full",
+    }
+}
+
+fn write_title(out: &mut String, title: &str) {
+    out.push('"');
+
+    for c in title.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c => out.push(c),
+        }
+    }
+
+    out.push('"');
+}
+
+fn write_view<T>(out: &mut String, view: &View<T>) {
+    let kind = match view.kind {
+        ViewKind::Document => "document",
+        ViewKind::Tool => "tool",
+    };
+
+    let _ = write!(out, "view {} {} {} {} {} {} ", view.handle.0, kind, view.flags.closable as u8,
+                   view.flags.movable as u8, view.flags.dockable as u8, view.tab_width);
+
+    match view.preferred_size {
+        Some((width, height)) => { let _ = write!(out, "{} {} ", width, height); }
+        None => out.push_str("- - "),
+    }
+
+    write_title(out, &view.title);
+    out.push('\n');
+}
+
+fn write_grid(out: &mut String, grid: &Grid) {
+    let _ = writeln!(out, "grid {} {} {}", grid.columns.len(), grid.rows.len(), grid.cells.len());
+
+    for track in grid.columns.iter().chain(grid.rows.iter()) {
+        let _ = match *track {
+            Track::Fixed(size) => writeln!(out, "track fixed {}", size),
+            Track::Fraction(fraction) => writeln!(out, "track fraction {}", fraction),
+            Track::Auto => writeln!(out, "track auto"),
+        };
+    }
+
+    for cell in &grid.cells {
+        let _ = writeln!(out, "cell {} {} {} {} {}", cell.view.0, cell.column, cell.row, cell.column_span,
+                         cell.row_span);
+    }
+}
+
+fn write_container<T>(out: &mut String, container: &Container<T>) {
+    let _ = writeln!(out, "container {} {}", container.fixed as u8, container.views.len());
+
+    for view in &container.views {
+        write_view(out, view);
+    }
+
+    if let Some(ref grid) = container.grid {
+        write_grid(out, grid);
+    }
+}
+
+fn write_split_line(out: &mut String, handle: SplitHandle, direction: Direction, default_ratio: f32, weights: &[f32]) {
+    let _ = write!(out, "split {} {} {}", handle.0, direction_name(direction), default_ratio);
+
+    for weight in weights {
+        let _ = write!(out, " {}", weight);
+    }
+
+    out.push('\n');
+}
+
+fn write_split<T>(out: &mut String, split: &Split<T>) {
+    let weights: Vec<f32> = split.children.iter().map(|child| child.weight).collect();
+    write_split_line(out, split.handle, split.direction, split.default_ratio, &weights);
+
+    for child in &split.children {
+        match child.split {
+            Some(ref split) => write_split(out, split),
+            None => write_container(out, &child.views),
+        }
+    }
+}
+
+/// Saved layouts only hold the handles of the views, their containers are written as `panel` lines
+fn write_skeleton(out: &mut String, skeleton: &Skeleton) {
+    let weights: Vec<f32> = skeleton.children.iter().map(|&(_, weight)| weight).collect();
+    write_split_line(out, skeleton.handle, skeleton.direction, skeleton.default_ratio, &weights);
+
+    for (side, _) in &skeleton.children {
+        match *side {
+            SkeletonSide::Split(ref split) => write_skeleton(out, split),
+            SkeletonSide::Container { ref views, fixed, ref grid } => {
+                let _ = write!(out, "panel {} {}", fixed as u8, views.len());
+
+                for view in views {
+                    let _ = write!(out, " {}", view.0);
+                }
+
+                out.push('\n');
+
+                if let Some(ref grid) = *grid {
+                    write_grid(out, grid);
+                }
+            }
+        }
+    }
+}
+
+/// A token together with the column (starting at 1) it starts at
+struct Token {
+    col: usize,
+    text: String,
+}
+
+/// Splits a line at whitespace. A quoted string is a single token with the escapes resolved
+fn tokenize(line: &str, line_number: usize) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = line.char_indices().peekable();
+
+    while let Some(&(index, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        let col = line[..index].chars().count() + 1;
+        let mut text = String::new();
+
+        if c == '"' {
+            chars.next();
+
+            loop {
+                match chars.next() {
+                    Some((_, '"')) => break,
+                    Some((_, '\\')) => match chars.next() {
+                        Some((_, 'n')) => text.push('\n'),
+                        Some((_, c)) => text.push(c),
+                        None => break,
+                    },
+                    Some((_, c)) => text.push(c),
+                    None => {
+                        return Err(Error::ParseError { line: line_number, col, message: "unterminated string".to_owned() });
+                    }
+                }
+            }
+        } else {
+            while let Some(&(_, c)) = chars.peek() {
+                if c.is_whitespace() {
+                    break;
+                }
+
+                text.push(c);
+                chars.next();
+            }
+        }
+
+        tokens.push(Token { col, text });
+    }
+
+    Ok(tokens)
+}
+
+/// Deepest nesting of splits `restore` accepts, so corrupted files can't overflow the stack
+const MAX_DEPTH: usize = 256;
+
+/// Reads the saved text line by line, skipping empty lines
+struct Parser<'a> {
+    lines: Vec<(usize, &'a str)>,
+    pos: usize,
+    /// Tokens of the current line and the index of the next one
+    tokens: Vec<Token>,
+    token: usize,
+    line: usize,
+    /// Splits the current split is nested in, see `MAX_DEPTH`
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(text: &'a str) -> Parser<'a> {
+        Parser {
+            lines: text.lines()
+                .enumerate()
+                .map(|(index, line)| (index + 1, line))
+                .filter(|(_, line)| !line.trim().is_empty())
+                .collect(),
+            pos: 0,
+            tokens: Vec::new(),
+            token: 0,
+            line: 0,
+            depth: 0,
+        }
+    }
+
+    fn error(&self, col: usize, message: String) -> Error {
+        Error::ParseError { line: self.line, col, message }
+    }
+
+    fn at_end(&self) -> bool {
+        self.pos >= self.lines.len()
+    }
+
+    /// Name of the next line without consuming it
+    fn peek(&self) -> Option<&'a str> {
+        self.lines.get(self.pos).and_then(|(_, line)| line.split_whitespace().next())
+    }
+
+    /// Moves to the next line which has to start with the keyword
+    fn expect_line(&mut self, keyword: &str) -> Result<()> {
+        let (line, text) = match self.lines.get(self.pos) {
+            Some(&line) => line,
+            None => {
+                let line = self.lines.last().map_or(1, |&(line, _)| line);
+                return Err(Error::ParseError { line, col: 1, message: format!("expected '{}' but the input ended", keyword) });
+            }
+        };
+
+        self.pos += 1;
+        self.line = line;
+        self.tokens = tokenize(text, line)?;
+        self.token = 1;
+
+        if self.tokens[0].text != keyword {
+            return Err(self.error(self.tokens[0].col, format!("expected '{}' but found '{}'", keyword, self.tokens[0].text)));
+        }
+
+        Ok(())
+    }
+
+    fn next(&mut self, what: &str) -> Result<&Token> {
+        if self.token >= self.tokens.len() {
+            let col = self.tokens.last().map_or(1, |token| token.col + token.text.chars().count());
+            return Err(self.error(col, format!("expected {}", what)));
+        }
+
+        self.token += 1;
+        Ok(&self.tokens[self.token - 1])
+    }
+
+    fn end_line(&self) -> Result<()> {
+        match self.tokens.get(self.token) {
+            Some(token) => Err(self.error(token.col, format!("unexpected '{}'", token.text))),
+            None => Ok(()),
+        }
+    }
+
+    fn number<N: ::std::str::FromStr>(&mut self, what: &str) -> Result<N> {
+        let (col, parsed) = {
+            let token = self.next(what)?;
+            (token.col, token.text.parse::<N>())
+        };
+
+        parsed.map_err(|_| self.error(col, format!("invalid {}", what)))
+    }
+
+    fn float(&mut self, what: &str) -> Result<f32> {
+        let col = self.tokens.get(self.token).map_or(1, |token| token.col);
+        let value: f32 = self.number(what)?;
+
+        if value.is_finite() {
+            Ok(value)
+        } else {
+            Err(self.error(col, format!("{} has to be finite", what)))
+        }
+    }
+
+    fn flag(&mut self, what: &str) -> Result<bool> {
+        let col = self.tokens.get(self.token).map_or(1, |token| token.col);
+
+        match self.number::<u8>(what)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(self.error(col, format!("{} has to be 0 or 1", what))),
+        }
+    }
+
+    fn keyword<V: Copy>(&mut self, what: &str, values: &[(&str, V)]) -> Result<V> {
+        let (col, text) = {
+            let token = self.next(what)?;
+            (token.col, token.text.clone())
+        };
+
+        values.iter()
+            .find(|&&(name, _)| name == text)
+            .map(|&(_, value)| value)
+            .ok_or_else(|| self.error(col, format!("unknown {} '{}'", what, text)))
+    }
+
+    fn view<T>(&mut self, payload: &mut dyn FnMut(ViewHandle) -> T) -> Result<View<T>> {
+        self.expect_line("view")?;
+
+        let handle = ViewHandle(self.number("view handle")?);
+        let kind = self.keyword("view kind", &[("document", ViewKind::Document), ("tool", ViewKind::Tool)])?;
+        let flags = ViewFlags {
+            closable: self.flag("closable flag")?,
+            movable: self.flag("movable flag")?,
+            dockable: self.flag("dockable flag")?,
+        };
+        let tab_width = self.float("tab width")?;
+
+        let preferred_size = if self.tokens.get(self.token).is_some_and(|token| token.text == "-") {
+            self.next("preferred width")?;
+            self.next("preferred height")?;
+            None
+        } else {
+            Some((self.float("preferred width")?, self.float("preferred height")?))
+        };
+
+        let title = self.next("title")?.text.clone();
+        self.end_line()?;
+
+        let mut view = View::new(handle, payload(handle));
+        view.kind = kind;
+        view.flags = flags;
+        view.tab_width = tab_width;
+        view.preferred_size = preferred_size;
+        view.title = title;
+
+        Ok(view)
+    }
+
+    fn container<T>(&mut self, payload: &mut dyn FnMut(ViewHandle) -> T) -> Result<Container<T>> {
+        self.expect_line("container")?;
+
+        let mut container = Container::new();
+        container.fixed = self.flag("fixed flag")?;
+        let count: usize = self.number("view count")?;
+        self.end_line()?;
+
+        for _ in 0..count {
+            container.views.push(self.view(payload)?);
+        }
+
+        if self.peek() == Some("grid") {
+            let views: Vec<ViewHandle> = container.views.iter().map(|view| view.handle).collect();
+            container.grid = Some(self.grid(&views)?);
+        }
+
+        Ok(container)
+    }
+
+    fn track(&mut self) -> Result<Track> {
+        self.expect_line("track")?;
+
+        let track = match self.keyword("track kind", &[("fixed", 0), ("fraction", 1), ("auto", 2)])? {
+            0 => Track::Fixed(self.float("track size")?),
+            1 => Track::Fraction(self.float("track fraction")?),
+            _ => Track::Auto,
+        };
+
+        self.end_line()?;
+
+        Ok(track)
+    }
+
+    fn grid(&mut self, views: &[ViewHandle]) -> Result<Grid> {
+        self.expect_line("grid")?;
+
+        let line = self.line;
+        let columns: usize = self.number("column count")?;
+        let rows: usize = self.number("row count")?;
+        let cells: usize = self.number("cell count")?;
+        self.end_line()?;
+
+        let mut grid = Grid::new(Vec::new(), Vec::new());
+
+        for _ in 0..columns {
+            let track = self.track()?;
+            grid.columns.push(track);
+        }
+
+        for _ in 0..rows {
+            let track = self.track()?;
+            grid.rows.push(track);
+        }
+
+        check_tracks(&grid.columns, &grid.rows).map_err(|err| Error::ParseError { line, col: 1, message: err.to_string() })?;
+
+        for _ in 0..cells {
+            self.expect_line("cell")?;
+
+            let col = self.tokens.get(self.token).map_or(1, |token| token.col);
+            let cell = GridCell {
+                view: ViewHandle(self.number("view handle")?),
+                column: self.number("column")?,
+                row: self.number("row")?,
+                column_span: self.number("column span")?,
+                row_span: self.number("row span")?,
+            };
+            self.end_line()?;
+
+            if !views.contains(&cell.view) || grid.cell(cell.view).is_some() {
+                return Err(self.error(col, format!("invalid grid cell for view {}", cell.view.0)));
+            }
+
+            grid.check_cell(&cell).map_err(|err| self.error(col, err.to_string()))?;
+
+            grid.cells.push(cell);
+        }
+
+        Ok(grid)
+    }
+
+    fn child<T>(&mut self, payload: &mut dyn FnMut(ViewHandle) -> T, weight: f32) -> Result<SplitChild<T>> {
+        let mut child = SplitChild::new(weight);
+
+        if self.peek() == Some("split") {
+            child.split = Some(self.split(payload)?);
+        } else {
+            child.views = self.container(payload)?;
+        }
+
+        Ok(child)
+    }
+
+    /// Handle, direction, default ratio and child weights of a split
+    fn split_line(&mut self) -> Result<(SplitHandle, Direction, f32, Vec<f32>)> {
+        self.expect_line("split")?;
+
+        if self.depth >= MAX_DEPTH {
+            return Err(self.error(1, format!("splits are nested deeper than {} levels", MAX_DEPTH)));
+        }
+
+        let handle = SplitHandle(self.number("split handle")?);
+        let direction = self.keyword("direction", &[("vertical", Direction::Vertical),
+                                                    ("horizontal", Direction::Horizontal),
+                                                    ("full", Direction::Full)])?;
+        let default_ratio = self.float("default ratio")?;
+
+        // The weights run until the end of the line, one per child
+        let mut weights = vec![self.float("weight")?, self.float("weight")?];
+
+        while self.token < self.tokens.len() {
+            weights.push(self.float("weight")?);
+        }
+
+        Ok((handle, direction, default_ratio, weights))
+    }
+
+    fn split<T>(&mut self, payload: &mut dyn FnMut(ViewHandle) -> T) -> Result<Box<Split<T>>> {
+        let (handle, direction, default_ratio, weights) = self.split_line()?;
+
+        let mut split = Box::new(Split::new(direction, handle));
+        split.default_ratio = default_ratio;
+
+        self.depth += 1;
+        split.children = weights.into_iter()
+            .map(|weight| self.child(payload, weight))
+            .collect::<Result<_>>()?;
+        self.depth -= 1;
+
+        Ok(split)
+    }
+
+    fn skeleton(&mut self) -> Result<Skeleton> {
+        let (handle, direction, default_ratio, weights) = self.split_line()?;
+
+        // Only the unused child of a full split has no space
+        if weights.iter().any(|&weight| weight < 0.0 || (weight == 0.0 && direction != Direction::Full)) {
+            return Err(self.error(1, format!("split {} has an invalid weight", handle.0)));
+        }
+
+        self.depth += 1;
+        let children = weights.into_iter()
+            .map(|weight| self.skeleton_side().map(|side| (side, weight)))
+            .collect::<Result<_>>()?;
+        self.depth -= 1;
+
+        Ok(Skeleton { handle, direction, default_ratio, children })
+    }
+
+    fn skeleton_side(&mut self) -> Result<SkeletonSide> {
+        if self.peek() == Some("split") {
+            return Ok(SkeletonSide::Split(Box::new(self.skeleton()?)));
+        }
+
+        self.expect_line("panel")?;
+
+        let fixed = self.flag("fixed flag")?;
+        let count: usize = self.number("view count")?;
+        let views = (0..count)
+            .map(|_| self.number("view handle").map(ViewHandle))
+            .collect::<Result<Vec<_>>>()?;
+        self.end_line()?;
+
+        let grid = if self.peek() == Some("grid") { Some(self.grid(&views)?) } else { None };
+
+        Ok(SkeletonSide::Container { views, fixed, grid })
+    }
+
+    fn area(&mut self) -> Result<DockArea> {
+        self.keyword("area", &[("center", DockArea::Center), ("left", DockArea::Left), ("right", DockArea::Right),
+                               ("top", DockArea::Top), ("bottom", DockArea::Bottom)])
+    }
+
+    fn zone_area(&mut self) -> Result<DockArea> {
+        self.keyword("dock zone", &[("left", DockArea::Left), ("right", DockArea::Right),
+                                    ("top", DockArea::Top), ("bottom", DockArea::Bottom)])
+    }
+
+    /// A layout saved with `Workspace::save_layout`, see `write_skeleton`
+    fn layout(&mut self) -> Result<(String, Snapshot)> {
+        self.expect_line("layout")?;
+
+        let name = self.next("layout name")?.text.clone();
+        self.end_line()?;
+
+        let mut snapshot = Snapshot { trees: Vec::new(), zone_sizes: Vec::new() };
+
+        while self.peek() == Some("zone") {
+            self.expect_line("zone")?;
+
+            let area = self.zone_area()?;
+            let size = self.float("zone size")?;
+            self.end_line()?;
+
+            snapshot.zone_sizes.push((area, size));
+        }
+
+        while self.peek() == Some("tree") {
+            self.expect_line("tree")?;
+
+            let area = self.area()?;
+            self.end_line()?;
+
+            let skeleton = self.skeleton()?;
+            snapshot.trees.push((area, skeleton));
+        }
+
+        Ok((name, snapshot))
+    }
+
+    fn workspace<T>(&mut self, counter: &Arc<AtomicU64>, payload: &mut dyn FnMut(ViewHandle) -> T)
+                    -> Result<(WorkspaceHandle, Workspace<T>)> {
+        self.expect_line("workspace")?;
+
+        let handle = WorkspaceHandle(self.number("workspace handle")?);
+        let col = self.tokens.get(self.token).map_or(1, |token| token.col);
+        let rect = Rect::new(self.float("x")?, self.float("y")?, self.float("width")?, self.float("height")?);
+        let scale = self.float("scale")?;
+        let pixel_exact = self.flag("pixel exact flag")?;
+        self.end_line()?;
+
+        let mut ws = Workspace::new(rect).map_err(|err| self.error(col, err.to_string()))?;
+        ws.handle_counter = counter.clone();
+        ws.pixel_exact = pixel_exact;
+
+        if scale <= 0.0 {
+            return Err(self.error(col, "scale has to be larger than 0.0".to_owned()));
+        }
+
+        ws.scale = scale;
+
+        while self.peek() == Some("zone") {
+            self.expect_line("zone")?;
+
+            let area = self.zone_area()?;
+            let size = self.float("zone size")?;
+            self.end_line()?;
+
+            if let Some(zone) = ws.dock_zone_mut(area) {
+                zone.size = size;
+            }
+        }
+
+        while self.peek() == Some("tree") {
+            self.expect_line("tree")?;
+
+            let area = self.area()?;
+            let line = self.line;
+            self.end_line()?;
+
+            *ws.tree_mut(area) = Some(self.split(payload)?);
+
+            ws.validate().map_err(|err| Error::ParseError { line, col: 1, message: err.to_string() })?;
+        }
+
+        while self.peek() == Some("floating") {
+            self.expect_line("floating")?;
+
+            let line = self.line;
+            let rect = Rect::new(self.float("x")?, self.float("y")?, self.float("width")?, self.float("height")?);
+            self.end_line()?;
+
+            let container = self.container(payload)?;
+
+            if container.views.is_empty() {
+                return Err(Error::ParseError { line, col: 1, message: "empty floating window".to_owned() });
+            }
+
+            check_window_rect(rect).map_err(|err| Error::ParseError { line, col: 1, message: err.to_string() })?;
+
+            for view in &container.views {
+                if ws.has_view(view.handle) {
+                    return Err(Error::DuplicateHandle(view.handle));
+                }
+            }
+
+            ws.floating.push(FloatingWindow { container, rect });
+        }
+
+        if self.peek() == Some("hidden") {
+            self.expect_line("hidden")?;
+
+            let count: usize = self.number("view count")?;
+            self.end_line()?;
+
+            for _ in 0..count {
+                let view = self.view(payload)?;
+
+                if ws.has_view(view.handle) || ws.is_hidden(view.handle) {
+                    return Err(Error::DuplicateHandle(view.handle));
+                }
+
+                ws.hidden.push(view);
+            }
+        }
+
+        while self.peek() == Some("layout") {
+            let line = self.lines[self.pos].0;
+            let (name, snapshot) = self.layout()?;
+
+            if ws.layouts.iter().any(|(other, _)| *other == name) {
+                return Err(Error::ParseError { line, col: 1, message: format!("layout '{}' is defined twice", name) });
+            }
+
+            ws.layouts.push((name, snapshot));
+        }
+
+        Ok((handle, ws))
+    }
+}
+
+impl<T> WorkspaceManager<T> {
+    ///
+    /// Saves all workspaces with their rects, scale, dock zones, split trees, floating windows,
+    /// hidden views and saved layouts together with the view titles, flags and sizes as text. User data and `Style`/`SnapConfig`
+    /// settings aren't saved, `restore_with` creates the user data of each view again.
+    ///
+    pub fn save(&self) -> String {
+        let mut out = String::new();
+
+        let _ = writeln!(out, "{}", HEADER);
+        let _ = writeln!(out, "handles {} {} {}", self.view_counter, self.handle_counter.load(Ordering::Relaxed),
+                         self.workspace_counter);
+
+        for (handle, ws) in &self.workspaces {
+            let rect = ws.rect;
+            let _ = writeln!(out, "workspace {} {} {} {} {} {} {}", handle.0, rect.x, rect.y, rect.width, rect.height,
+                             ws.scale(), ws.pixel_exact as u8);
+
+            for &area in &DockArea::ALL[1..] {
+                if let Some(zone) = ws.dock_zone(area) {
+                    let _ = writeln!(out, "zone {} {}", area_name(area), zone.size);
+                }
+            }
+
+            for &area in &DockArea::ALL {
+                if let Some(ref split) = *ws.tree(area) {
+                    let _ = writeln!(out, "tree {}", area_name(area));
+                    write_split(&mut out, split);
+                }
+            }
+
+            for window in ws.floating_windows() {
+                let rect = window.rect;
+                let _ = writeln!(out, "floating {} {} {} {}", rect.x, rect.y, rect.width, rect.height);
+                write_container(&mut out, &window.container);
+            }
+
+            if ws.hidden_views().next().is_some() {
+                let _ = writeln!(out, "hidden {}", ws.hidden_views().count());
+
+                for view in ws.hidden_views() {
+                    write_view(&mut out, view);
+                }
+            }
+
+            for (name, snapshot) in &ws.layouts {
+                out.push_str("layout ");
+                write_title(&mut out, name);
+                out.push('\n');
+
+                for &(area, size) in &snapshot.zone_sizes {
+                    let _ = writeln!(out, "zone {} {}", area_name(area), size);
+                }
+
+                for (area, skeleton) in &snapshot.trees {
+                    let _ = writeln!(out, "tree {}", area_name(*area));
+                    write_skeleton(&mut out, skeleton);
+                }
+            }
+        }
+
+        out
+    }
+
+    /// Restores workspaces saved with `save`, every view gets the default user data
+    pub fn restore(text: &str) -> Result<WorkspaceManager<T>> where T: Default {
+        Self::restore_with(text, |_| T::default())
+    }
+
+    ///
+    /// Restores workspaces saved with `save` and creates the user data of each view with `payload`.
+    /// Fails with `Error::ParseError` if the text is malformed or a tree is invalid and with
+    /// `Error::DuplicateHandle` if a view handle is used by more than one workspace.
+    ///
+    pub fn restore_with<F>(text: &str, mut payload: F) -> Result<WorkspaceManager<T>> where F: FnMut(ViewHandle) -> T {
+        let mut parser = Parser::new(text);
+
+        parser.expect_line("viewdock")?;
+
+        let version: u32 = parser.number("version")?;
+
+        if version != 2 {
+            return Err(parser.error(parser.tokens[1].col, format!("unsupported version {}", version)));
+        }
+
+        parser.end_line()?;
+        parser.expect_line("handles")?;
+
+        let mut manager = WorkspaceManager::new();
+        manager.view_counter = parser.number("view counter")?;
+        let split_counter: u64 = parser.number("split counter")?;
+        manager.workspace_counter = parser.number("workspace counter")?;
+        parser.end_line()?;
+
+        manager.handle_counter.store(split_counter, Ordering::Relaxed);
+
+        while !parser.at_end() {
+            let counter = manager.handle_counter.clone();
+            let (handle, ws) = parser.workspace(&counter, &mut payload)?;
+
+            if manager.workspace(handle).is_some() {
+                return Err(parser.error(1, format!("workspace {} is defined twice", handle.0)));
+            }
+
+            for view in ws.views().map(|(view, _)| view).chain(ws.hidden_views()) {
+                if manager.find_view(view.handle).is_some() {
+                    return Err(Error::DuplicateHandle(view.handle));
+                }
+            }
+
+            manager.workspace_counter = manager.workspace_counter.max(handle.0);
+            manager.workspaces.push((handle, ws));
+        }
+
+        // Make sure new handles don't clash with the restored ones, even if the counters were edited
+        let mut max_split = split_counter;
+        let mut max_view = manager.view_counter;
+
+        for (_, ws) in &manager.workspaces {
+            for split in ws.trees() {
+                split.max_handles(&mut max_split, &mut max_view);
+            }
+
+            for view in ws.views().map(|(view, _)| view).chain(ws.hidden_views()) {
+                max_view = max_view.max(view.handle.0);
+            }
+
+            // Switching to a saved layout brings back its split handles
+            for (_, snapshot) in &ws.layouts {
+                for (_, skeleton) in &snapshot.trees {
+                    skeleton.max_handles(&mut max_split, &mut max_view);
+                }
+            }
+        }
+
+        manager.handle_counter.store(max_split, Ordering::Relaxed);
+        manager.view_counter = max_view;
+
+        Ok(manager)
+    }
+}
+
+impl<T> Split<T> {
+    fn max_handles(&self, split: &mut u64, view: &mut u64) {
+        *split = (*split).max(self.handle.0);
+
+        for child in &self.children {
+            for other in &child.views.views {
+                *view = (*view).max(other.handle.0);
+            }
+
+            if let Some(ref child) = child.split {
+                child.max_handles(split, view);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod test {
+    use {Direction, DockArea, Error, Rect, Track, ViewHandle, ViewKind, WorkspaceManager};
+
+    fn manager() -> WorkspaceManager<String> {
+        let mut manager = WorkspaceManager::new();
+        let main = manager.add_workspace(Rect::new(0.0, 0.0, 1024.0, 768.0)).unwrap();
+        let second = manager.add_workspace(Rect::new(100.0, 50.0, 640.0, 480.0)).unwrap();
+
+        let (editor, output, explorer, tool) = (manager.new_view_handle(), manager.new_view_handle(),
+                                                manager.new_view_handle(), manager.new_view_handle());
+
+        let ws = manager.workspace_mut(main).unwrap();
+        ws.split_top_with(editor, Direction::Vertical, "editor".to_owned()).unwrap();
+        ws.split_by_view_handle_with(Direction::Horizontal, editor, output, "output".to_owned()).unwrap();
+        ws.dock_view_with(DockArea::Left, explorer, "explorer".to_owned()).unwrap();
+        ws.dock_zone_mut(DockArea::Left).unwrap().size = 180.0;
+        ws.make_grid(explorer, vec![Track::Fixed(120.0), Track::Fraction(1.5)], vec![Track::Auto]).unwrap();
+
+        let view = ws.view_mut(editor).unwrap();
+        view.title = "main.rs \"modified\"".to_owned();
+        view.kind = ViewKind::Document;
+        view.flags.closable = false;
+        view.preferred_size = Some((300.0, 200.0));
+
+        let ws = manager.workspace_mut(second).unwrap();
+        ws.split_top_with(tool, Direction::Vertical, "tool".to_owned()).unwrap();
+        ws.set_scale(1.5).unwrap();
+        ws.float_view(tool, Rect::new(20.0, 30.0, 200.0, 150.0)).unwrap();
+
+        manager
+    }
+
+    #[test]
+    fn test_save_restore_roundtrip() {
+        let manager = manager();
+        let text = manager.save();
+
+        let restored = WorkspaceManager::<String>::restore_with(&text, |handle| format!("view {}", handle.0)).unwrap();
+        assert_eq!(restored.save(), text);
+
+        let (main, ws) = restored.workspaces().next().unwrap();
+        let editor = ws.view(ViewHandle(1)).unwrap();
+
+        assert_eq!(editor.title, "main.rs \"modified\"");
+        assert_eq!(editor.kind, ViewKind::Document);
+        assert_eq!(editor.flags.closable, false);
+        assert_eq!(editor.preferred_size, Some((300.0, 200.0)));
+        assert_eq!(editor.payload(), "view 1");
+        assert_eq!(ws.area_of(ViewHandle(3)), Some(DockArea::Left));
+        assert_eq!(ws.dock_zone(DockArea::Left).unwrap().size, 180.0);
+        assert_eq!(ws.grid(ViewHandle(3)).unwrap().columns[1], Track::Fraction(1.5));
+        assert_eq!(ws.grid(ViewHandle(3)).unwrap().cell(ViewHandle(3)).unwrap().column, 0);
+        assert_eq!(restored.find_view(ViewHandle(4)).is_some_and(|handle| handle != main), true);
+        assert_eq!(restored.workspaces().nth(1).unwrap().1.scale(), 1.5);
+        assert_eq!(restored.workspaces().nth(1).unwrap().1.floating_window(ViewHandle(4)).unwrap().rect.width, 200.0);
+
+        // New handles continue after the restored ones
+        let mut restored = restored;
+        assert_eq!(restored.new_view_handle(), ViewHandle(5));
+    }
+
+    #[test]
+    fn test_roundtrip_multi_child_split() {
+        let mut manager: WorkspaceManager = WorkspaceManager::new();
+        let handle = manager.add_workspace(Rect::new(0.0, 0.0, 900.0, 600.0)).unwrap();
+        let views = [manager.new_view_handle(), manager.new_view_handle(), manager.new_view_handle()];
+
+        let ws = manager.workspace_mut(handle).unwrap();
+        ws.split_top(views[0], Direction::Vertical).unwrap();
+        ws.split_by_view_handle_n(Direction::Vertical, views[0], &views[1..]).unwrap();
+        ws.update();
+
+        let text = manager.save();
+        let mut restored = WorkspaceManager::<()>::restore(&text).unwrap();
+        assert_eq!(restored.save(), text);
+
+        let ws = restored.workspace_mut(handle).unwrap();
+        ws.update();
+        assert_eq!(ws.split.as_ref().unwrap().children.len(), 3);
+        assert_eq!(ws.view(views[2]).unwrap().rect.x, 600.0);
+    }
+
+    #[test]
+    fn test_restore_rejects_bad_grid_cells() {
+        let mut manager: WorkspaceManager = WorkspaceManager::new();
+        let handle = manager.add_workspace(Rect::new(0.0, 0.0, 800.0, 600.0)).unwrap();
+        let (first, second) = (manager.new_view_handle(), manager.new_view_handle());
+
+        let ws = manager.workspace_mut(handle).unwrap();
+        ws.split_top(first, Direction::Vertical).unwrap();
+        ws.add_tab(first, second).unwrap();
+        ws.make_grid(first, vec![Track::Fraction(1.0), Track::Fraction(1.0)], vec![Track::Fraction(1.0)]).unwrap();
+
+        let text = manager.save();
+        let cell = format!("cell {} 1 0 1 1", second.0);
+        assert_eq!(text.contains(&cell), true);
+
+        // A span that overflows and a cell on top of the other view
+        for bad in &[format!("cell {} 1 0 18446744073709551615 1", second.0), format!("cell {} 0 0 1 1", second.0)] {
+            match WorkspaceManager::<()>::restore(&text.replace(&cell, bad)) {
+                Err(Error::ParseError { .. }) => (),
+                _ => panic!("expected ParseError for '{}'", bad),
+            }
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_hidden_views_and_layouts() {
+        let mut manager: WorkspaceManager<String> = WorkspaceManager::new();
+        let handle = manager.add_workspace(Rect::new(0.0, 0.0, 800.0, 600.0)).unwrap();
+        let (editor, watch) = (manager.new_view_handle(), manager.new_view_handle());
+
+        let ws = manager.workspace_mut(handle).unwrap();
+        ws.split_top_with(editor, Direction::Vertical, "editor".to_owned()).unwrap();
+        ws.save_layout("Code");
+        ws.dock_view_with(DockArea::Bottom, watch, "watch".to_owned()).unwrap();
+        ws.view_mut(watch).unwrap().title = "Watch".to_owned();
+        ws.make_grid(watch, vec![Track::Fraction(1.0)], vec![Track::Auto]).unwrap();
+        ws.save_layout("Debug");
+        ws.switch_layout("Code").unwrap();
+
+        let text = manager.save();
+        let mut restored = WorkspaceManager::<String>::restore_with(&text, |handle| format!("view {}", handle.0)).unwrap();
+        assert_eq!(restored.save(), text);
+
+        // The hidden view is still owned by the workspace and comes back with the layout
+        assert_eq!(restored.find_view(watch), Some(handle));
+        assert_eq!(restored.new_view_handle(), ViewHandle(3));
+
+        let ws = restored.workspace_mut(handle).unwrap();
+        assert_eq!(ws.hidden_views().map(|view| view.title.as_str()).collect::<Vec<_>>(), vec!["Watch"]);
+        assert_eq!(ws.layout_names().collect::<Vec<_>>(), vec!["Code", "Debug"]);
+
+        let diff = ws.switch_layout("Debug").unwrap();
+        assert_eq!(diff.shown, vec![watch]);
+        assert_eq!(ws.area_of(watch), Some(DockArea::Bottom));
+        assert_eq!(ws.grid(watch).is_some(), true);
+        assert_eq!(ws.validate().is_ok(), true);
+    }
+
+    #[test]
+    fn test_restore_errors() {
+        let text = manager().save();
+
+        match WorkspaceManager::<()>::restore(&text.replace("split 2 horizontal", "split 2 diagonal")) {
+            Err(Error::ParseError { line: 9, col: 9, .. }) => (),
+            other => panic!("unexpected result {:?}", other.err()),
+        }
+
+        match WorkspaceManager::<()>::restore(&text.replace("view 4 tool", "view 1 tool")) {
+            Err(Error::DuplicateHandle(ViewHandle(1))) => (),
+            other => panic!("unexpected result {:?}", other.err()),
+        }
+
+        assert_eq!(WorkspaceManager::<()>::restore("viewdock 3\n").is_err(), true);
+        assert_eq!(WorkspaceManager::<()>::restore(&text.replace("track fraction 1.5", "track fraction 0")).is_err(), true);
+        // The full split in the left zone is missing its empty right container
+        let end = text.find("container 0 0\nworkspace").unwrap();
+        assert_eq!(WorkspaceManager::<()>::restore(&text[..end]).is_err(), true);
+    }
+
+    #[test]
+    fn test_restore_rejects_deep_nesting() {
+        let text = manager().save();
+
+        // Far more splits than the stack could take, each nesting into its first child
+        let nested = "split 100 vertical 0.5 0.5 0.5\n".repeat(200_000);
+        let start = text.find("split 2 horizontal").unwrap();
+
+        match WorkspaceManager::<()>::restore(&format!("{}{}{}", &text[..start], nested, &text[start..])) {
+            Err(Error::ParseError { ref message, .. }) if message.contains("nested") => (),
+            other => panic!("unexpected result {:?}", other.err()),
+        }
+    }
+}
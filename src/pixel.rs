@@ -0,0 +1,146 @@
+use {Rect, Split, ViewHandle, Workspace};
+
+/// Rect in whole pixels, for example for software renderers
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct IntRect {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl IntRect {
+    pub fn new(x: i32, y: i32, width: i32, height: i32) -> IntRect {
+        IntRect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+}
+
+impl Rect {
+    ///
+    /// Rounds the edges of the rect to whole pixels. The edges are rounded instead of the size so
+    /// two rects that share an edge still share it after rounding.
+    ///
+    pub fn round(&self) -> Rect {
+        let x = self.x.round();
+        let y = self.y.round();
+
+        Rect::new(x, y, (self.x + self.width).round() - x, (self.y + self.height).round() - y)
+    }
+
+    /// Same as `round` but as an `IntRect`
+    pub fn to_int(&self) -> IntRect {
+        let rect = self.round();
+        IntRect::new(rect.x as i32, rect.y as i32, rect.width as i32, rect.height as i32)
+    }
+}
+
+impl<T> Split<T> {
+    fn collect_int_rects(&self, out: &mut Vec<(ViewHandle, IntRect)>) {
+        for child in &self.children {
+            if let Some(ref split) = child.split {
+                split.collect_int_rects(out);
+            }
+
+            let rect = child.views.rect.to_int();
+
+            match child.views.grid {
+                // The cell of a view is its title bar and its content
+                Some(_) => out.extend(child.views.views.iter().map(|view| {
+                    let tab = view.tab_rect;
+                    (view.handle, Rect::new(tab.x, tab.y, tab.width, view.rect.y + view.rect.height - tab.y).to_int())
+                })),
+                None => out.extend(child.views.views.iter().map(|view| (view.handle, rect))),
+            }
+        }
+    }
+}
+
+impl<T> Workspace<T> {
+    ///
+    /// Rects of all views in whole pixels as computed by the last `update`. Views in the same
+    /// container share its rect, views in a grid get the rect of their cell. The rects of different
+    /// containers don't overlap, their edges are rounded the same way `pixel_exact` rounds them so
+    /// neighbours share their edges also when it's off.
+    ///
+    pub fn int_rects(&self) -> Vec<(ViewHandle, IntRect)> {
+        let mut rects = Vec::new();
+
+        for split in self.trees() {
+            split.collect_int_rects(&mut rects);
+        }
+
+        rects
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod test {
+    use {tabs, IntRect, Preset, Rect, Track, ViewHandle, Workspace};
+
+    fn workspace() -> Workspace {
+        let views = [ViewHandle(1), ViewHandle(2), ViewHandle(3), ViewHandle(4)];
+        Workspace::<()>::from_preset(Rect::new(0.0, 0.0, 1001.0, 601.0), Preset::Ide, &views).unwrap()
+    }
+
+    fn overlaps(a: IntRect, b: IntRect) -> bool {
+        a.x < b.x + b.width && b.x < a.x + a.width && a.y < b.y + b.height && b.y < a.y + a.height
+    }
+
+    fn check_tiling(ws: &Workspace) {
+        let rects = ws.int_rects();
+        let area: i32 = rects.iter().map(|&(_, rect)| rect.width * rect.height).sum();
+
+        assert_eq!(area, 1001 * 601);
+
+        for (i, &(_, a)) in rects.iter().enumerate() {
+            for &(_, b) in &rects[i + 1..] {
+                assert_eq!(overlaps(a, b), false);
+            }
+        }
+    }
+
+    #[test]
+    fn test_pixel_exact_layout() {
+        let mut ws = workspace();
+        ws.pixel_exact = true;
+        ws.update();
+
+        let split = ws.split.as_ref().unwrap();
+        let right = split.right().as_ref().unwrap();
+
+        for rect in &[split.left_views().rect, right.right_views().rect] {
+            assert_eq!(rect.x.fract(), 0.0);
+            assert_eq!(rect.width.fract(), 0.0);
+        }
+
+        assert_eq!(split.left_views().rect.x + split.left_views().rect.width, right.rect.x);
+        check_tiling(&ws);
+    }
+
+    #[test]
+    fn test_int_rects_without_pixel_exact() {
+        let ws = workspace();
+
+        assert_eq!(ws.split.as_ref().unwrap().left_views().rect.width.fract() != 0.0, true);
+        check_tiling(&ws);
+    }
+
+    #[test]
+    fn test_int_rects_of_grid_cells() {
+        let mut ws: Workspace = Workspace::from_layout(Rect::new(0.0, 0.0, 1001.0, 601.0), tabs(&[1, 2, 3, 4])).unwrap();
+        ws.make_grid(ViewHandle(1), vec![Track::Fraction(1.0), Track::Fraction(1.0)],
+                     vec![Track::Fraction(1.0), Track::Fraction(2.0)]).unwrap();
+        ws.update();
+
+        let rects = ws.int_rects();
+        assert_eq!(rects[0], (ViewHandle(1), IntRect::new(0, 0, 501, 200)));
+        assert_eq!(rects[3], (ViewHandle(4), IntRect::new(501, 200, 500, 401)));
+        check_tiling(&ws);
+    }
+}
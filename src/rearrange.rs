@@ -0,0 +1,210 @@
+use {Direction, DockArea, Error, GridAxis, Result, Split, SplitHandle, Workspace};
+
+impl<T> Split<T> {
+    /// Turns a side by side split into a stacked one and the other way around
+    pub fn rotate(&mut self) {
+        self.dirty = true;
+        self.direction = match self.direction {
+            Direction::Vertical => Direction::Horizontal,
+            Direction::Horizontal => Direction::Vertical,
+            Direction::Full => Direction::Full,
+        };
+    }
+
+    /// Swaps the left/top and right/bottom side by reversing the children, which keep their size
+    pub fn swap(&mut self) {
+        if self.direction == Direction::Full {
+            return;
+        }
+
+        self.children.reverse();
+        self.dirty = true;
+        self.default_ratio = 1.0 - self.default_ratio;
+    }
+
+    /// Swaps the sides of this split and all splits below it that are in the direction, grids below
+    /// it reverse their tracks along it
+    fn mirror(&mut self, direction: Direction) {
+        if self.direction == direction {
+            self.swap();
+        }
+
+        for child in &mut self.children {
+            if let Some(ref mut split) = child.split {
+                split.mirror(direction);
+                self.dirty |= split.dirty;
+            }
+
+            if let Some(ref mut grid) = child.views.grid {
+                grid.mirror(grid_axis(direction));
+                self.dirty = true;
+            }
+        }
+    }
+}
+
+/// Axis of the grid tracks that run in the direction of a split
+fn grid_axis(direction: Direction) -> GridAxis {
+    match direction {
+        Direction::Vertical => GridAxis::Columns,
+        _ => GridAxis::Rows,
+    }
+}
+
+impl<T> Workspace<T> {
+    fn find_split_for_rearrange(&mut self, handle: SplitHandle) -> Result<&mut Split<T>> {
+        let split = self.find_split_mut(handle)
+            .ok_or(Error::SplitNotFound(handle))?;
+
+        if split.direction == Direction::Full {
+            return Err(Error::ConstraintViolation("a Direction::Full split can't be rearranged".to_owned()));
+        }
+
+        Ok(split)
+    }
+
+    /// Toggles the direction of the split between `Direction::Vertical` and `Direction::Horizontal`
+    pub fn rotate_split(&mut self, handle: SplitHandle) -> Result<()> {
+        self.find_split_for_rearrange(handle)?.rotate();
+        self.debug_validate();
+        Ok(())
+    }
+
+    /// Swaps the two sides of the split while keeping their sizes. More children are reversed
+    pub fn swap_split(&mut self, handle: SplitHandle) -> Result<()> {
+        self.find_split_for_rearrange(handle)?.swap();
+        self.debug_validate();
+        Ok(())
+    }
+
+    ///
+    /// Mirrors the whole layout left to right, for example for right-to-left languages. The left and
+    /// right dock zones trade places, grids reverse their columns and floating windows are mirrored
+    /// about the middle of the workspace.
+    ///
+    pub fn mirror_horizontal(&mut self) {
+        self.swap_zones(DockArea::Left, DockArea::Right);
+        self.mirror(Direction::Vertical);
+    }
+
+    /// Mirrors the whole layout top to bottom like `mirror_horizontal`. The top and bottom dock zones trade places.
+    pub fn mirror_vertical(&mut self) {
+        self.swap_zones(DockArea::Top, DockArea::Bottom);
+        self.mirror(Direction::Horizontal);
+    }
+
+    fn mirror(&mut self, direction: Direction) {
+        for split in self.trees_mut() {
+            split.mirror(direction);
+        }
+
+        let rect = self.rect;
+
+        for window in &mut self.floating {
+            match direction {
+                Direction::Vertical => window.rect.x = 2.0 * rect.x + rect.width - window.rect.x - window.rect.width,
+                _ => window.rect.y = 2.0 * rect.y + rect.height - window.rect.y - window.rect.height,
+            }
+
+            if let Some(ref mut grid) = window.container.grid {
+                grid.mirror(grid_axis(direction));
+            }
+        }
+
+        self.debug_validate();
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod test {
+    use test_util::check_range;
+    use {tabs, view, Direction, Layout, Rect, Track, ViewHandle, Workspace};
+
+    fn workspace() -> Workspace {
+        // A | (B / C)
+        let layout = Layout::vertical(0.25)
+            .left(view(1))
+            .right(Layout::horizontal(0.4).top(view(2)).bottom(view(3)));
+
+        Workspace::<()>::from_layout(Rect::new(0.0, 0.0, 1000.0, 1000.0), layout).unwrap()
+    }
+
+    #[test]
+    fn test_rotate_split() {
+        let mut ws = workspace();
+        let handle = ws.split.as_ref().unwrap().handle;
+
+        ws.rotate_split(handle).unwrap();
+        ws.update();
+
+        let split = ws.split.as_ref().unwrap();
+        assert_eq!(split.direction, Direction::Horizontal);
+        assert_eq!(check_range(split.left_views().rect.height, 250.0, 0.01), true);
+    }
+
+    #[test]
+    fn test_swap_split_keeps_sizes() {
+        let mut ws = workspace();
+        let handle = ws.split.as_ref().unwrap().handle;
+
+        ws.swap_split(handle).unwrap();
+        ws.update();
+
+        let split = ws.split.as_ref().unwrap();
+        assert_eq!(split.right_views().views[0].handle, ViewHandle(1));
+        assert_eq!(check_range(split.right_views().rect.x, 750.0, 0.01), true);
+        assert_eq!(check_range(split.right_views().rect.width, 250.0, 0.01), true);
+        assert_eq!(split.left().is_some(), true);
+    }
+
+    #[test]
+    fn test_mirror() {
+        let mut ws = workspace();
+
+        ws.mirror_horizontal();
+        ws.update();
+
+        {
+            let split = ws.split.as_ref().unwrap();
+            let stacked = split.left().as_ref().unwrap();
+
+            assert_eq!(check_range(split.right_views().rect.x, 750.0, 0.01), true);
+            // Horizontal splits are left alone
+            assert_eq!(stacked.left_views().views[0].handle, ViewHandle(2));
+        }
+
+        ws.mirror_vertical();
+        ws.update();
+
+        let split = ws.split.as_ref().unwrap();
+        let stacked = split.left().as_ref().unwrap();
+
+        assert_eq!(stacked.left_views().views[0].handle, ViewHandle(3));
+        assert_eq!(check_range(stacked.left_views().rect.height, 600.0, 0.01), true);
+    }
+
+    #[test]
+    fn test_mirror_grids_and_floating_windows() {
+        let layout = Layout::vertical(0.5).left(view(1)).right(tabs(&[2, 3, 4]));
+        let mut ws = Workspace::<()>::from_layout(Rect::new(0.0, 0.0, 1000.0, 1000.0), layout).unwrap();
+        ws.make_grid(ViewHandle(2), vec![Track::Fixed(100.0), Track::Fraction(1.0)], vec![Track::Fraction(1.0); 2]).unwrap();
+        ws.set_grid_cell(ViewHandle(4), 0, 1, 2, 1).unwrap();
+        ws.float_view(ViewHandle(1), Rect::new(100.0, 50.0, 200.0, 100.0)).unwrap();
+
+        ws.mirror_horizontal();
+        ws.update();
+
+        let grid = ws.grid(ViewHandle(2)).unwrap();
+        assert_eq!(grid.columns, vec![Track::Fraction(1.0), Track::Fixed(100.0)]);
+        assert_eq!((grid.cell(ViewHandle(2)).unwrap().column, grid.cell(ViewHandle(3)).unwrap().column), (1, 0));
+        assert_eq!((grid.cell(ViewHandle(4)).unwrap().column, grid.cell(ViewHandle(4)).unwrap().column_span), (0, 2));
+        assert_eq!(ws.view(ViewHandle(2)).unwrap().rect.x, 900.0);
+        assert_eq!(ws.floating_window(ViewHandle(1)).unwrap().rect.x, 700.0);
+
+        ws.mirror_vertical();
+        let grid = ws.grid(ViewHandle(2)).unwrap();
+        assert_eq!((grid.cell(ViewHandle(2)).unwrap().row, grid.cell(ViewHandle(4)).unwrap().row), (1, 0));
+        assert_eq!(ws.floating_window(ViewHandle(1)).unwrap().rect.y, 850.0);
+    }
+}
@@ -0,0 +1,308 @@
+use {Direction, Error, Rect, Result, Split, SplitChild, ViewHandle, Workspace, MIN_RATIO};
+
+/// How space is redistributed when the size of the workspace changes
+#[derive(Debug, Clone, Copy)]
+pub enum ResizeStrategy {
+    /// Keep all ratios so every container scales with the workspace
+    Proportional,
+    /// Keep the size of containers marked as `fixed` and give the rest of the space to the others
+    KeepFixed,
+    /// Keep the size of the container holding the view, usually the one that has focus
+    KeepView(ViewHandle),
+}
+
+fn extent(direction: Direction, rect: Rect) -> f32 {
+    match direction {
+        Direction::Vertical => rect.width,
+        Direction::Horizontal => rect.height,
+        Direction::Full => 0.0,
+    }
+}
+
+/// A child is fixed if it's a fixed container or a split where every container is fixed
+fn is_child_fixed<T>(child: &SplitChild<T>) -> bool {
+    match child.split {
+        Some(ref split) => split.children.iter().all(is_child_fixed),
+        None => child.views.fixed,
+    }
+}
+
+/// Raises weights below `min` to it and takes the space from the other weights in proportion
+fn clamp_weights(weights: &mut [f32], min: f32) {
+    let mut raised = vec![false; weights.len()];
+
+    loop {
+        let mut changed = false;
+
+        for (weight, raised) in weights.iter_mut().zip(raised.iter_mut()) {
+            if !*raised && *weight < min {
+                *weight = min;
+                *raised = true;
+                changed = true;
+            }
+        }
+
+        let rest: f32 = weights.iter().zip(&raised).filter(|&(_, &raised)| !raised).map(|(weight, _)| weight).sum();
+
+        if !changed || rest <= 0.0 {
+            return;
+        }
+
+        let used = min * raised.iter().filter(|&&raised| raised).count() as f32;
+        let scale = (1.0 - used) / rest;
+
+        for (weight, _) in weights.iter_mut().zip(&raised).filter(|&(_, &raised)| !raised) {
+            *weight *= scale;
+        }
+    }
+}
+
+impl<T> Split<T> {
+    /// Children whose size the strategy keeps. Keeping all of them is the same as keeping none
+    fn kept_children(&self, strategy: ResizeStrategy) -> Vec<bool> {
+        let kept: Vec<bool> = match strategy {
+            ResizeStrategy::Proportional => vec![false; self.children.len()],
+            ResizeStrategy::KeepFixed => self.children.iter().map(is_child_fixed).collect(),
+            ResizeStrategy::KeepView(handle) => self.children.iter().map(|child| child.has_view(handle)).collect(),
+        };
+
+        if kept.iter().all(|&keep| keep) {
+            vec![false; kept.len()]
+        } else {
+            kept
+        }
+    }
+
+    ///
+    /// Calculates new weights when the split goes from the old to the new rect. The size of a kept
+    /// child is multiplied by `factor`, which is used when the scale factor changes.
+    ///
+    fn resize(&mut self, old_rect: Rect, new_rect: Rect, strategy: ResizeStrategy, min_size: f32, factor: f32) {
+        if let Direction::Full = self.direction {
+            return;
+        }
+
+        let old_rects = self.child_rects(old_rect, false);
+        let old_extent = extent(self.direction, old_rect);
+        let new_extent = extent(self.direction, new_rect);
+
+        let kept = self.kept_children(strategy);
+        let mut weights: Vec<f32> = self.children.iter().map(|child| child.weight).collect();
+
+        // Kept children get their old size, the others share the rest in their old proportions
+        if kept.contains(&true) {
+            let kept_weight: f32 = weights.iter().zip(&kept).filter(|&(_, &keep)| keep)
+                .map(|(weight, _)| old_extent * weight * factor / new_extent)
+                .sum();
+            let free_weight: f32 = weights.iter().zip(&kept).filter(|&(_, &keep)| !keep).map(|(weight, _)| weight).sum();
+
+            for (weight, &keep) in weights.iter_mut().zip(&kept) {
+                *weight = if keep {
+                    old_extent * *weight * factor / new_extent
+                } else {
+                    *weight / free_weight * (1.0 - kept_weight)
+                };
+            }
+        }
+
+        // Re-clamp against the minimum size. If the split is too small to fit all children the space
+        // is shared equally instead.
+        let count = self.children.len() as f32;
+
+        if new_extent > min_size * count {
+            clamp_weights(&mut weights, min_size / new_extent);
+        } else {
+            weights.iter_mut().for_each(|weight| *weight = 1.0 / count);
+        }
+
+        for (child, weight) in self.children.iter_mut().zip(weights) {
+            child.weight = weight.clamp(MIN_RATIO, 1.0 - MIN_RATIO);
+        }
+
+        self.dirty = true;
+
+        let new_rects = self.child_rects(new_rect, false);
+
+        for (child, (old_rect, new_rect)) in self.children.iter_mut().zip(old_rects.into_iter().zip(new_rects)) {
+            if let Some(ref mut split) = child.split {
+                split.resize(old_rect, new_rect, strategy, min_size, factor);
+            }
+        }
+    }
+}
+
+impl<T> Workspace<T> {
+    ///
+    /// Resize the workspace and redistribute the space using the strategy. Ratios are adjusted so
+    /// containers stay at least `Style::min_panel_size` large where possible. The rect follows the same rules
+    /// as `Workspace::new` and `update` is called afterwards.
+    ///
+    pub fn set_rect(&mut self, rect: Rect, strategy: ResizeStrategy) -> Result<()> {
+        Self::validate_rect(rect)?;
+
+        let old_rects = self.area_rects();
+        let min_size = self.metrics().min_panel_size;
+
+        self.rect = rect;
+        let new_rects = self.area_rects();
+
+        for (split, (old_rect, new_rect)) in self.roots_mut().zip(old_rects.iter().zip(new_rects.iter())) {
+            if let Some(ref mut split) = *split {
+                split.resize(*old_rect, *new_rect, strategy, min_size, 1.0);
+            }
+        }
+
+        self.update();
+        self.debug_validate();
+
+        Ok(())
+    }
+
+    ///
+    /// Changes the scale factor, for example when the window moves to a monitor with another DPI.
+    /// Ratios are kept except for containers marked as `fixed` which keep their size in logical
+    /// units. If the size of the window changes as well call `set_rect` with
+    /// `ResizeStrategy::KeepFixed` afterwards.
+    ///
+    pub fn set_scale(&mut self, scale: f32) -> Result<()> {
+        if !(scale > 0.0 && scale.is_finite()) {
+            return Err(Error::IllegalSize(format!("scale {} has to be larger than 0.0", scale)));
+        }
+
+        let old_rects = self.area_rects();
+        let factor = scale / self.scale;
+        let min_size = self.style.min_panel_size * scale;
+
+        self.scale = scale;
+        let new_rects = self.area_rects();
+
+        for (split, (old_rect, new_rect)) in self.roots_mut().zip(old_rects.iter().zip(new_rects.iter())) {
+            if let Some(ref mut split) = *split {
+                split.resize(*old_rect, *new_rect, ResizeStrategy::KeepFixed, min_size, factor);
+            }
+        }
+
+        self.update();
+        self.debug_validate();
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod test {
+    use test_util::check_range;
+    use {Direction, Rect, ResizeStrategy, ViewHandle, Workspace};
+
+    /// View 1 to the left, view 2 and 3 stacked on the right
+    fn workspace() -> Workspace {
+        let mut ws: Workspace = Workspace::new(Rect::new(0.0, 0.0, 1000.0, 1000.0)).unwrap();
+        ws.split_top(ViewHandle(1), Direction::Vertical).unwrap();
+        ws.split_top(ViewHandle(2), Direction::Vertical).unwrap();
+        ws.split_by_view_handle(Direction::Horizontal, ViewHandle(2), ViewHandle(3)).unwrap();
+        ws.split.as_mut().unwrap().set_ratio(0.2);
+        ws.update();
+        ws
+    }
+
+    fn view_rect(ws: &Workspace, handle: ViewHandle) -> Rect {
+        let split = ws.split.as_ref().unwrap();
+
+        if handle.0 == 1 {
+            split.left_views().rect
+        } else {
+            let right = split.right().as_ref().unwrap();
+            if right.left_views().views[0].handle == handle {
+                right.left_views().rect
+            } else {
+                right.right_views().rect
+            }
+        }
+    }
+
+    #[test]
+    fn test_set_rect_invalid() {
+        let mut ws = workspace();
+        assert_eq!(ws.set_rect(Rect::new(0.0, 0.0, 0.0, 100.0), ResizeStrategy::Proportional).is_err(), true);
+    }
+
+    #[test]
+    fn test_set_rect_proportional() {
+        let mut ws = workspace();
+        ws.set_rect(Rect::new(0.0, 0.0, 2000.0, 500.0), ResizeStrategy::Proportional).unwrap();
+
+        assert_eq!(check_range(view_rect(&ws, ViewHandle(1)).width, 400.0, 0.01), true);
+        assert_eq!(check_range(view_rect(&ws, ViewHandle(2)).height, 250.0, 0.01), true);
+    }
+
+    #[test]
+    fn test_set_rect_keep_fixed() {
+        let mut ws = workspace();
+        ws.split.as_mut().unwrap().left_views_mut().fixed = true;
+        ws.set_rect(Rect::new(0.0, 0.0, 2000.0, 500.0), ResizeStrategy::KeepFixed).unwrap();
+
+        assert_eq!(check_range(view_rect(&ws, ViewHandle(1)).width, 200.0, 0.01), true);
+        assert_eq!(check_range(view_rect(&ws, ViewHandle(2)).width, 1800.0, 0.01), true);
+    }
+
+    #[test]
+    fn test_set_rect_keep_view() {
+        let mut ws = workspace();
+        ws.set_rect(Rect::new(0.0, 0.0, 1500.0, 1500.0), ResizeStrategy::KeepView(ViewHandle(3))).unwrap();
+
+        let rect = view_rect(&ws, ViewHandle(3));
+        assert_eq!(check_range(rect.width, 800.0, 0.01), true);
+        assert_eq!(check_range(rect.height, 500.0, 0.01), true);
+        assert_eq!(check_range(view_rect(&ws, ViewHandle(1)).width, 700.0, 0.01), true);
+    }
+
+    #[test]
+    fn test_set_rect_clamps_to_min_size() {
+        let mut ws = workspace();
+        ws.split.as_mut().unwrap().left_views_mut().fixed = true;
+        ws.set_rect(Rect::new(0.0, 0.0, 220.0, 500.0), ResizeStrategy::KeepFixed).unwrap();
+
+        assert_eq!(check_range(view_rect(&ws, ViewHandle(2)).width, ws.style.min_panel_size, 0.01), true);
+    }
+
+    #[test]
+    fn test_set_scale() {
+        let mut ws = workspace();
+        ws.split.as_mut().unwrap().left_views_mut().fixed = true;
+
+        assert_eq!(ws.set_scale(0.0).is_err(), true);
+
+        ws.set_scale(2.0).unwrap();
+        assert_eq!(check_range(view_rect(&ws, ViewHandle(1)).width, 400.0, 0.01), true);
+        // The stacked views on the right keep their ratio
+        assert_eq!(check_range(view_rect(&ws, ViewHandle(2)).height, 500.0, 0.01), true);
+
+        ws.set_scale(1.0).unwrap();
+        assert_eq!(check_range(view_rect(&ws, ViewHandle(1)).width, 200.0, 0.01), true);
+    }
+
+    #[test]
+    fn test_scaled_min_size() {
+        let mut ws = workspace();
+        ws.set_scale(2.0).unwrap();
+        ws.split.as_mut().unwrap().left_views_mut().fixed = true;
+        ws.set_rect(Rect::new(0.0, 0.0, 220.0, 500.0), ResizeStrategy::KeepFixed).unwrap();
+
+        assert_eq!(check_range(view_rect(&ws, ViewHandle(2)).width, ws.style.min_panel_size * 2.0, 0.01), true);
+    }
+
+    #[test]
+    fn test_set_rect_keep_view_in_multi_child_split() {
+        let mut ws: Workspace = Workspace::new(Rect::new(0.0, 0.0, 900.0, 500.0)).unwrap();
+        ws.split_top(ViewHandle(1), Direction::Vertical).unwrap();
+        ws.split_by_view_handle_n(Direction::Vertical, ViewHandle(1), &[ViewHandle(2), ViewHandle(3)]).unwrap();
+        ws.set_rect(Rect::new(0.0, 0.0, 1500.0, 500.0), ResizeStrategy::KeepView(ViewHandle(2))).unwrap();
+
+        // The other two children share the new space in their old proportions
+        let widths: Vec<f32> = (1..4).map(|handle| ws.view(ViewHandle(handle)).unwrap().rect.width).collect();
+        assert_eq!(check_range(widths[0], 600.0, 0.01), true);
+        assert_eq!(check_range(widths[1], 300.0, 0.01), true);
+        assert_eq!(check_range(widths[2], 600.0, 0.01), true);
+    }
+}
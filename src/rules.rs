@@ -0,0 +1,190 @@
+use {Direction, DockArea, Error, Result, Split, View, ViewHandle, Workspace};
+
+/// Where a view goes relative to its target
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DropSide {
+    Left,
+    Right,
+    Top,
+    Bottom,
+    /// Into the same container as the target, as a new tab
+    Tab,
+}
+
+impl DropSide {
+    /// Side of a view that is split off in the direction, `first` being the left/top side
+    pub(crate) fn from_direction(direction: Direction, first: bool) -> DropSide {
+        match (direction, first) {
+            (Direction::Horizontal, true) => DropSide::Top,
+            (Direction::Horizontal, false) => DropSide::Bottom,
+            (_, true) => DropSide::Left,
+            (_, false) => DropSide::Right,
+        }
+    }
+}
+
+/// What a view is placed next to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DropTarget {
+    /// Next to or in the same container as the view
+    View(ViewHandle),
+    /// Into the document area or one of the dock zones
+    Area(DockArea),
+}
+
+impl DockArea {
+    /// The side used when asking the rules about placing a view into the area: the edge of the
+    /// workspace the dock zone is on and `DropSide::Tab` for the document area
+    pub(crate) fn side(self) -> DropSide {
+        match self {
+            DockArea::Center => DropSide::Tab,
+            DockArea::Left => DropSide::Left,
+            DockArea::Right => DropSide::Right,
+            DockArea::Top => DropSide::Top,
+            DockArea::Bottom => DropSide::Bottom,
+        }
+    }
+}
+
+///
+/// Decides if a view may be placed at a target. Consulted before every split, move, tab and dock
+/// and used to leave out drop zones, see `Workspace::set_rules`. Closures taking
+/// `(&View<T>, DropTarget, DropSide)` implement it as well.
+///
+pub trait DockRules<T> {
+    /// Returns true if `view` may be put on `side` of `target`. For `DropSide::Tab` this is asked
+    /// once for every view already in the target container.
+    fn allow(&self, view: &View<T>, target: DropTarget, side: DropSide) -> bool;
+}
+
+impl<T, F> DockRules<T> for F where F: Fn(&View<T>, DropTarget, DropSide) -> bool {
+    fn allow(&self, view: &View<T>, target: DropTarget, side: DropSide) -> bool {
+        self(view, target, side)
+    }
+}
+
+impl<T> Split<T> {
+    ///
+    /// Returns true if `split_by_view_handle` puts a new view before the container of `find_handle`
+    /// in the split it ends up in: into the first free child of the split holding `find_handle` if
+    /// there is one, otherwise before the container only if it's the first child.
+    ///
+    pub(crate) fn inserts_first(&self, find_handle: ViewHandle) -> Option<bool> {
+        let free = self.children.iter().position(|child| child.split.is_none() && child.views.views.is_empty());
+        let found = self.children.iter()
+            .position(|child| child.views.views.iter().any(|view| view.handle == find_handle));
+
+        if let Some(index) = found {
+            return Some(match free {
+                Some(free) => free < index,
+                None => index == 0,
+            });
+        }
+
+        self.children.iter()
+            .filter_map(|child| child.split.as_ref())
+            .find_map(|split| split.inserts_first(find_handle))
+    }
+}
+
+impl<T> Workspace<T> {
+    /// Sets the rules consulted before views are placed, replacing the previous ones
+    pub fn set_rules<R>(&mut self, rules: R) where R: DockRules<T> + 'static {
+        self.rules = Some(Box::new(rules));
+    }
+
+    /// Removes the rules so every placement is allowed again, apart from the built-in ones
+    pub fn clear_rules(&mut self) {
+        self.rules = None;
+    }
+
+    /// Returns true if the rules allow the placement. Tabs have to be allowed by every view in the container
+    pub(crate) fn allows(&self, view: &View<T>, target: DropTarget, side: DropSide) -> bool {
+        let rules = match self.rules {
+            Some(ref rules) => rules,
+            None => return true,
+        };
+
+        match (target, side) {
+            (DropTarget::View(handle), DropSide::Tab) => {
+                match self.trees().filter_map(|split| split.find_container(handle)).next() {
+                    Some(container) => container.views.iter()
+                        .filter(|other| other.handle != view.handle)
+                        .all(|other| rules.allow(view, DropTarget::View(other.handle), side)),
+                    None => rules.allow(view, target, side),
+                }
+            }
+
+            _ => rules.allow(view, target, side),
+        }
+    }
+
+    /// Fails with `Error::ConstraintViolation` if the built-in rules or the user rules reject the placement
+    pub(crate) fn check_rules(&self, view: &View<T>, target: DropTarget, side: DropSide) -> Result<()> {
+        let area = match target {
+            DropTarget::View(handle) => self.area_of(handle).ok_or(Error::ViewNotFound(handle))?,
+            DropTarget::Area(area) => area,
+        };
+
+        self.check_area(view, area)?;
+
+        if !self.allows(view, target, side) {
+            return Err(Error::ConstraintViolation(format!("view {} can't be placed {:?} of {:?}",
+                                                          view.handle.0, side, target)));
+        }
+
+        Ok(())
+    }
+
+    /// Side a new view goes to when the container of `find_handle` is split in the direction
+    pub(crate) fn insert_side(&self, direction: Direction, find_handle: ViewHandle) -> Result<DropSide> {
+        let first = self.trees()
+            .filter_map(|split| split.inserts_first(find_handle))
+            .next()
+            .ok_or(Error::ViewNotFound(find_handle))?;
+
+        Ok(DropSide::from_direction(direction, first))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod test {
+    use test_util::workspace;
+    use {Direction, DockArea, DropSide, DropTarget, Error, View, ViewHandle};
+
+    #[test]
+    fn test_rules_reject_split() {
+        let mut ws = workspace();
+
+        // View 3 may only go above other views
+        ws.set_rules(|view: &View, _target: DropTarget, side: DropSide| view.handle != ViewHandle(3) || side == DropSide::Top);
+
+        match ws.split_by_view_handle(Direction::Vertical, ViewHandle(1), ViewHandle(3)) {
+            Err(Error::ConstraintViolation(_)) => (),
+            _ => panic!("expected the rules to reject the split"),
+        }
+
+        assert_eq!(ws.has_view(ViewHandle(3)), false);
+        assert_eq!(ws.split_by_view_handle(Direction::Horizontal, ViewHandle(1), ViewHandle(3)).is_ok(), true);
+
+        ws.clear_rules();
+        assert_eq!(ws.move_view(Direction::Vertical, ViewHandle(2), ViewHandle(3)).is_ok(), true);
+    }
+
+    #[test]
+    fn test_rules_restrict_dock_zone() {
+        let mut ws = workspace();
+
+        // Only the right dock zone takes views
+        ws.set_rules(|_view: &View, target: DropTarget, _side: DropSide| match target {
+            DropTarget::Area(area) => area == DockArea::Right || area == DockArea::Center,
+            DropTarget::View(_) => true,
+        });
+
+        assert_eq!(ws.move_to_dock(DockArea::Left, ViewHandle(2)).is_err(), true);
+        assert_eq!(ws.area_of(ViewHandle(2)), Some(DockArea::Center));
+        assert_eq!(ws.move_to_dock(DockArea::Right, ViewHandle(2)).is_ok(), true);
+        assert_eq!(ws.area_of(ViewHandle(2)), Some(DockArea::Right));
+    }
+}
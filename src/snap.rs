@@ -0,0 +1,221 @@
+use {Direction, Error, Rect, Result, Split, SplitHandle, Workspace};
+
+/// Where sizers snap to while being dragged, see `Workspace::snap`
+#[derive(Debug, Clone)]
+pub struct SnapConfig {
+    /// Positions between the two neighbouring panels of a sizer, 0.5 is in the middle
+    pub ratios: Vec<f32>,
+    /// Also snap to edges of other panels along the same axis
+    pub edges: bool,
+    /// Distance from a snap point where the sizer locks to it, in logical units scaled by `Workspace::scale`
+    pub tolerance: f32,
+}
+
+impl Default for SnapConfig {
+    fn default() -> SnapConfig {
+        SnapConfig {
+            ratios: vec![1.0 / 3.0, 0.5, 2.0 / 3.0],
+            edges: true,
+            tolerance: 8.0,
+        }
+    }
+}
+
+impl SnapConfig {
+    /// Returns the closest snap point within tolerance of `pos` or `pos` if there is none
+    pub(crate) fn snap(&self, pos: f32, start: f32, length: f32, edges: &[f32]) -> f32 {
+        let ratios = self.ratios.iter().map(|ratio| start + length * ratio);
+        let edges = edges.iter().cloned().filter(|edge| *edge > start && *edge < start + length);
+
+        let mut best = pos;
+        let mut best_distance = self.tolerance;
+
+        for point in ratios.chain(edges) {
+            let distance = (point - pos).abs();
+
+            if distance <= best_distance {
+                best = point;
+                best_distance = distance;
+            }
+        }
+
+        best
+    }
+}
+
+impl<T> Split<T> {
+    fn collect_leaf_rects(&self, out: &mut Vec<Rect>) {
+        for child in &self.children {
+            match child.split {
+                Some(ref split) => split.collect_leaf_rects(out),
+                None if !child.views.views.is_empty() => out.push(child.views.rect),
+                None => (),
+            }
+        }
+    }
+}
+
+/// Edges along the axis of the direction of panels that don't overlap `rect`
+pub(crate) fn edges_outside(leaves: &[Rect], rect: Rect, direction: Direction) -> Vec<f32> {
+    let mut edges = Vec::new();
+
+    for leaf in leaves {
+        let overlaps = leaf.x < rect.x + rect.width && rect.x < leaf.x + leaf.width &&
+                       leaf.y < rect.y + rect.height && rect.y < leaf.y + leaf.height;
+
+        if overlaps {
+            continue;
+        }
+
+        match direction {
+            Direction::Vertical => edges.extend_from_slice(&[leaf.x, leaf.x + leaf.width]),
+            Direction::Horizontal => edges.extend_from_slice(&[leaf.y, leaf.y + leaf.height]),
+            Direction::Full => (),
+        }
+    }
+
+    edges
+}
+
+impl<T> Workspace<T> {
+    /// Rects of all containers as computed by the last `update`
+    pub(crate) fn leaf_rects(&self) -> Vec<Rect> {
+        let mut rects = Vec::new();
+
+        for split in self.trees() {
+            split.collect_leaf_rects(&mut rects);
+        }
+
+        rects
+    }
+
+    ///
+    /// Resets the ratio of the split to its `default_ratio`, a split with more than two children
+    /// gives all of them the same weight. Meant to be called when a sizer is double-clicked.
+    ///
+    pub fn reset_sizer(&mut self, handle: SplitHandle) -> Result<()> {
+        let split = self.find_split_mut(handle)
+            .ok_or(Error::SplitNotFound(handle))?;
+
+        if split.direction == Direction::Full {
+            return Err(Error::ConstraintViolation("a Direction::Full split has no sizer".to_owned()));
+        }
+
+        if split.children.len() > 2 {
+            split.set_equal_weights();
+        } else {
+            split.set_ratio(split.default_ratio);
+        }
+
+        split.drag_offset = 0.0;
+
+        self.debug_validate();
+
+        Ok(())
+    }
+
+    ///
+    /// Ends a drag of a sizer of the split. A snapped sizer remembers how far the pointer is from
+    /// it during a drag, this forgets it so the next drag starts with the sizer under the pointer.
+    /// Meant to be called when the pointer is released.
+    ///
+    pub fn end_sizer_drag(&mut self, handle: SplitHandle) -> Result<()> {
+        let offset = self.find_split(handle).ok_or(Error::SplitNotFound(handle))?.drag_offset;
+
+        // `find_split_mut` marks the split dirty, only a snapped sizer has something to reset
+        if offset != 0.0 {
+            if let Some(split) = self.find_split_mut(handle) {
+                split.drag_offset = 0.0;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod test {
+    use test_util::check_range;
+    use {view, Layout, Rect, SnapConfig, Workspace};
+
+    fn workspace() -> Workspace {
+        let layout = Layout::vertical(0.4).left(view(1)).right(view(2));
+        let mut ws: Workspace = Workspace::from_layout(Rect::new(0.0, 0.0, 900.0, 600.0), layout).unwrap();
+        ws.snap = Some(SnapConfig::default());
+        ws
+    }
+
+    #[test]
+    fn test_reset_sizer() {
+        let mut ws = workspace();
+        let handle = ws.split.as_ref().unwrap().handle;
+
+        ws.snap = None;
+        ws.drag_sizer(handle, (-100.0, 0.0)).unwrap();
+        assert_eq!(check_range(ws.split.as_ref().unwrap().ratio(), 0.4 + 100.0 / 900.0, 0.001), true);
+
+        ws.reset_sizer(handle).unwrap();
+        assert_eq!(check_range(ws.split.as_ref().unwrap().ratio(), 0.4, 0.001), true);
+    }
+
+    #[test]
+    fn test_snap_locks_within_tolerance() {
+        let mut ws = workspace();
+        let handle = ws.split.as_ref().unwrap().handle;
+
+        // Sizer at 360, pointer moves to 445 which is close to 450 (1/2)
+        ws.drag_sizer(handle, (-85.0, 0.0)).unwrap();
+        ws.update();
+        assert_eq!(check_range(ws.split.as_ref().unwrap().ratio(), 0.5, 0.001), true);
+
+        // Pointer at 449, still locked
+        ws.drag_sizer(handle, (-4.0, 0.0)).unwrap();
+        ws.update();
+        assert_eq!(check_range(ws.split.as_ref().unwrap().ratio(), 0.5, 0.001), true);
+
+        // Pointer at 439, out of range so the sizer follows it again
+        ws.drag_sizer(handle, (10.0, 0.0)).unwrap();
+        ws.update();
+        assert_eq!(check_range(ws.split.as_ref().unwrap().ratio() * 900.0, 439.0, 0.01), true);
+    }
+
+    #[test]
+    fn test_drags_start_without_offset() {
+        let mut ws = workspace();
+        let handle = ws.split.as_ref().unwrap().handle;
+
+        // Pointer at 445 locks the sizer at 450
+        ws.drag_sizer(handle, (-85.0, 0.0)).unwrap();
+        ws.end_sizer_drag(handle).unwrap();
+        ws.update();
+        assert_eq!(check_range(ws.split.as_ref().unwrap().ratio(), 0.5, 0.001), true);
+
+        // A new drag starts at the sizer, not where the pointer was during the last one
+        ws.drag_sizer(handle, (200.0, 0.0)).unwrap();
+        ws.end_sizer_drag(handle).unwrap();
+        ws.update();
+        assert_eq!(check_range(ws.split.as_ref().unwrap().ratio() * 900.0, 250.0, 0.01), true);
+
+        // Releasing the pointer without a drag doesn't lay anything out again
+        ws.end_sizer_drag(handle).unwrap();
+        assert_eq!(ws.split.as_ref().unwrap().is_dirty(), false);
+    }
+
+    #[test]
+    fn test_snap_to_neighbour_edge() {
+        // Two rows where the bottom sizer can line up with the top one at 250
+        let layout = Layout::horizontal(0.5)
+            .top(Layout::vertical(0.25).left(view(1)).right(view(2)))
+            .bottom(Layout::vertical(0.3).left(view(3)).right(view(4)));
+
+        let mut ws: Workspace = Workspace::from_layout(Rect::new(0.0, 0.0, 1000.0, 600.0), layout).unwrap();
+        ws.snap = Some(SnapConfig { ratios: Vec::new(), edges: true, tolerance: 8.0 });
+
+        let bottom = ws.split.as_ref().unwrap().right().as_ref().unwrap().handle;
+        ws.drag_sizer(bottom, (45.0, 0.0)).unwrap();
+
+        let ratio = ws.split.as_ref().unwrap().right().as_ref().unwrap().ratio();
+        assert_eq!(check_range(ratio, 0.25, 0.001), true);
+    }
+}
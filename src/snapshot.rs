@@ -0,0 +1,411 @@
+use std::collections::HashMap;
+use {Container, Direction, DockArea, DropTarget, Error, Grid, Result, Split, SplitChild, SplitHandle, View, ViewHandle, Workspace};
+
+/// Shape of a split tree with only the handles of the views, see `Workspace::save_layout`
+pub(crate) struct Skeleton {
+    pub(crate) handle: SplitHandle,
+    pub(crate) direction: Direction,
+    pub(crate) default_ratio: f32,
+    pub(crate) children: Vec<(SkeletonSide, f32)>,
+}
+
+pub(crate) enum SkeletonSide {
+    Split(Box<Skeleton>),
+    Container { views: Vec<ViewHandle>, fixed: bool, grid: Option<Grid> },
+}
+
+/// A named layout of all areas of a workspace
+pub(crate) struct Snapshot {
+    pub(crate) trees: Vec<(DockArea, Skeleton)>,
+    pub(crate) zone_sizes: Vec<(DockArea, f32)>,
+}
+
+/// Views that change visibility when switching layouts, see `Workspace::switch_layout`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LayoutDiff {
+    /// Views that were hidden before and are visible in the new layout
+    pub shown: Vec<ViewHandle>,
+    /// Views that are visible now but not part of the new layout. They are kept as hidden views
+    pub hidden: Vec<ViewHandle>,
+    /// Views in the new layout that don't exist anymore, the layout is applied without them
+    pub missing: Vec<ViewHandle>,
+}
+
+/// Result of building one side of a tree from a skeleton
+enum Built<T> {
+    Split(Box<Split<T>>),
+    Container(Container<T>),
+    Empty,
+}
+
+impl Skeleton {
+    fn new<T>(split: &Split<T>) -> Skeleton {
+        let side = |child: &SplitChild<T>| match child.split {
+            Some(ref split) => SkeletonSide::Split(Box::new(Skeleton::new(split))),
+            None => SkeletonSide::Container {
+                views: child.views.views.iter().map(|view| view.handle).collect(),
+                fixed: child.views.fixed,
+                grid: child.views.grid.clone(),
+            },
+        };
+
+        Skeleton {
+            handle: split.handle,
+            direction: split.direction,
+            default_ratio: split.default_ratio,
+            children: split.children.iter().map(|child| (side(child), child.weight)).collect(),
+        }
+    }
+
+    /// Raises the split and view handles to the largest ones used by the skeleton
+    pub(crate) fn max_handles(&self, split: &mut u64, view: &mut u64) {
+        *split = (*split).max(self.handle.0);
+
+        for (side, _) in &self.children {
+            match *side {
+                SkeletonSide::Split(ref skeleton) => skeleton.max_handles(split, view),
+                SkeletonSide::Container { ref views, .. } => {
+                    *view = views.iter().fold(*view, |max, handle| max.max(handle.0));
+                }
+            }
+        }
+    }
+
+    fn collect_views(&self, out: &mut Vec<ViewHandle>) {
+        for (side, _) in &self.children {
+            match *side {
+                SkeletonSide::Split(ref split) => split.collect_views(out),
+                SkeletonSide::Container { ref views, .. } => out.extend_from_slice(views),
+            }
+        }
+    }
+
+    ///
+    /// Builds the tree with the views taken out of the pool. Views that aren't in the pool are
+    /// left out, the remaining children share the space of the ones that are gone and a split
+    /// that is left with one child is replaced by it.
+    ///
+    fn build<T>(&self, pool: &mut HashMap<ViewHandle, View<T>>) -> Built<T> {
+        let mut built: Vec<(Built<T>, f32)> = self.children.iter().map(|&(ref side, weight)| {
+            let built = match *side {
+                SkeletonSide::Split(ref split) => split.build(pool),
+                SkeletonSide::Container { ref views, fixed, ref grid } => {
+                    let mut container = Container::new();
+                    container.fixed = fixed;
+                    // Cells of views that are missing are dropped by the next update
+                    container.grid = grid.clone();
+                    container.views = views.iter().filter_map(|handle| pool.remove(handle)).collect();
+
+                    if container.views.is_empty() { Built::Empty } else { Built::Container(container) }
+                }
+            };
+
+            (built, weight)
+        }).collect();
+
+        built.retain(|(built, _)| !matches!(*built, Built::Empty));
+
+        if let Direction::Full = self.direction {
+            return match built.into_iter().next() {
+                Some((Built::Container(container), _)) => Built::Split(Box::new(Split::full(self.handle, container))),
+                _ => Built::Empty,
+            };
+        }
+
+        if built.len() < 2 {
+            return built.pop().map_or(Built::Empty, |(built, _)| built);
+        }
+
+        let total: f32 = built.iter().map(|&(_, weight)| weight).sum();
+
+        let mut split = Box::new(Split::new(self.direction, self.handle));
+        split.default_ratio = self.default_ratio;
+        split.children = built.into_iter().map(|(built, weight)| {
+            let mut child = SplitChild::new(weight / total);
+
+            match built {
+                Built::Split(built) => child.split = Some(built),
+                Built::Container(built) => child.views = built,
+                Built::Empty => (),
+            }
+
+            child
+        }).collect();
+
+        Built::Split(split)
+    }
+}
+
+impl<T> Workspace<T> {
+    ///
+    /// Saves the current layout of all areas under the name, replacing a layout with the same
+    /// name. Only the shape of the trees, the weights and which views go where is saved, the views
+    /// themselves stay in the workspace.
+    ///
+    pub fn save_layout(&mut self, name: &str) {
+        let snapshot = Snapshot {
+            trees: DockArea::ALL.iter()
+                .filter_map(|&area| self.tree(area).as_ref().map(|split| (area, Skeleton::new(split))))
+                .collect(),
+            zone_sizes: DockArea::ALL.iter()
+                .filter_map(|&area| self.dock_zone(area).map(|zone| (area, zone.size)))
+                .collect(),
+        };
+
+        match self.layouts.iter_mut().find(|(other, _)| other == name) {
+            Some(&mut (_, ref mut layout)) => *layout = snapshot,
+            None => self.layouts.push((name.to_owned(), snapshot)),
+        }
+    }
+
+    /// Names of the saved layouts in the order they were first saved
+    pub fn layout_names(&self) -> impl Iterator<Item = &str> {
+        self.layouts.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Deletes the saved layout, the views in the workspace aren't touched
+    pub fn remove_layout(&mut self, name: &str) -> Result<()> {
+        let index = self.layout_index(name)?;
+        self.layouts.remove(index);
+        Ok(())
+    }
+
+    fn layout_index(&self, name: &str) -> Result<usize> {
+        self.layouts.iter()
+            .position(|(other, _)| other == name)
+            .ok_or_else(|| Error::LayoutNotFound(name.to_owned()))
+    }
+
+    /// Returns which views `switch_layout` would show, hide or miss without switching. Floating views count as visible
+    pub fn layout_diff(&self, name: &str) -> Result<LayoutDiff> {
+        let snapshot = &self.layouts[self.layout_index(name)?].1;
+        let mut target = Vec::new();
+
+        for (_, tree) in &snapshot.trees {
+            tree.collect_views(&mut target);
+        }
+
+        let visible: Vec<ViewHandle> = self.views().map(|(view, _)| view.handle).collect();
+
+        Ok(LayoutDiff {
+            shown: target.iter().cloned().filter(|handle| self.is_hidden(*handle)).collect(),
+            hidden: visible.iter().cloned().filter(|handle| !target.contains(handle)).collect(),
+            missing: target.iter().cloned().filter(|handle| !visible.contains(handle) && !self.is_hidden(*handle)).collect(),
+        })
+    }
+
+    ///
+    /// Switches to the saved layout. Views that aren't part of it are hidden with their user data
+    /// kept alive, hidden views that are part of it are shown again and views of the layout that
+    /// don't exist anymore are left out. Layouts don't have floating windows, floating views are
+    /// docked where the layout has them or hidden. `update` has to be called afterwards.
+    ///
+    pub fn switch_layout(&mut self, name: &str) -> Result<LayoutDiff> {
+        let diff = self.layout_diff(name)?;
+        let index = self.layout_index(name)?;
+
+        let mut pool: HashMap<ViewHandle, View<T>> = HashMap::new();
+
+        for root in self.roots_mut() {
+            if let Some(split) = root.take() {
+                split.into_views(&mut pool);
+            }
+        }
+
+        for window in self.floating.drain(..) {
+            for view in window.container.views {
+                pool.insert(view.handle, view);
+            }
+        }
+
+        // Views that stay hidden keep their order, newly hidden ones follow in layout order
+        let mut order: Vec<ViewHandle> = self.hidden.iter().map(|view| view.handle).collect();
+        order.extend_from_slice(&diff.hidden);
+
+        for view in self.hidden.drain(..) {
+            pool.insert(view.handle, view);
+        }
+
+        let snapshot = &self.layouts[index].1;
+        let mut trees = Vec::new();
+
+        for &(area, ref skeleton) in &snapshot.trees {
+            let root = match skeleton.build(&mut pool) {
+                Built::Split(split) => Some(split),
+                Built::Container(container) => Some(Box::new(Split::full(skeleton.handle, container))),
+                Built::Empty => None,
+            };
+
+            trees.push((area, root));
+        }
+
+        let sizes = snapshot.zone_sizes.clone();
+
+        for (area, root) in trees {
+            *self.tree_mut(area) = root;
+        }
+
+        for (area, size) in sizes {
+            if let Some(zone) = self.dock_zone_mut(area) {
+                zone.size = size;
+            }
+        }
+
+        self.hidden = order.iter().filter_map(|handle| pool.remove(handle)).collect();
+
+        self.debug_validate();
+
+        Ok(diff)
+    }
+
+    /// Views that were hidden by `switch_layout`
+    pub fn hidden_views(&self) -> impl Iterator<Item = &View<T>> {
+        self.hidden.iter()
+    }
+
+    pub(crate) fn is_hidden(&self, handle: ViewHandle) -> bool {
+        self.hidden.iter().any(|view| view.handle == handle)
+    }
+
+    /// Shows a hidden view again by docking it into the area
+    pub fn show_view(&mut self, area: DockArea, handle: ViewHandle) -> Result<()> {
+        let index = self.hidden.iter().position(|view| view.handle == handle).ok_or(Error::ViewNotFound(handle))?;
+        self.check_rules(&self.hidden[index], DropTarget::Area(area), area.side())?;
+
+        let view = self.hidden.remove(index);
+        self.dock(area, view)
+    }
+
+    /// Removes a hidden view for good and returns its user data
+    pub fn remove_hidden_view(&mut self, handle: ViewHandle) -> Result<T> {
+        let index = self.hidden.iter().position(|view| view.handle == handle).ok_or(Error::ViewNotFound(handle))?;
+        Ok(self.hidden.remove(index).into_payload())
+    }
+}
+
+impl<T> Split<T> {
+    fn into_views(self, pool: &mut HashMap<ViewHandle, View<T>>) {
+        for child in self.children {
+            if let Some(split) = child.split {
+                split.into_views(pool);
+            }
+
+            for view in child.views.views {
+                pool.insert(view.handle, view);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod test {
+    use {Direction, DockArea, Error, LayoutDiff, Rect, ViewHandle, Workspace};
+
+    fn workspace() -> Workspace<String> {
+        let mut ws = Workspace::new(Rect::new(0.0, 0.0, 1000.0, 800.0)).unwrap();
+        ws.split_top_with(ViewHandle(1), Direction::Vertical, "editor".to_owned()).unwrap();
+        ws.split_by_view_handle_with(Direction::Horizontal, ViewHandle(1), ViewHandle(2), "output".to_owned()).unwrap();
+        ws.dock_view_with(DockArea::Left, ViewHandle(3), "explorer".to_owned()).unwrap();
+        ws
+    }
+
+    #[test]
+    fn test_switch_layouts() {
+        let mut ws = workspace();
+        ws.save_layout("Code");
+
+        // Debug has the output in the right zone and the explorer next to the editor
+        ws.move_to_dock(DockArea::Right, ViewHandle(2)).unwrap();
+        ws.move_view(Direction::Vertical, ViewHandle(1), ViewHandle(3)).unwrap();
+        ws.dock_zone_mut(DockArea::Right).unwrap().size = 100.0;
+        ws.save_layout("Debug");
+
+        assert_eq!(ws.layout_names().collect::<Vec<_>>(), vec!["Code", "Debug"]);
+
+        let diff = ws.switch_layout("Code").unwrap();
+        assert_eq!(diff, LayoutDiff::default());
+        assert_eq!(ws.area_of(ViewHandle(2)), Some(DockArea::Center));
+        assert_eq!(ws.area_of(ViewHandle(3)), Some(DockArea::Left));
+        assert_eq!(ws.split.as_ref().unwrap().direction, Direction::Horizontal);
+        assert_eq!(ws.validate().is_ok(), true);
+
+        ws.switch_layout("Debug").unwrap();
+        ws.update();
+        assert_eq!(ws.area_of(ViewHandle(2)), Some(DockArea::Right));
+        assert_eq!(ws.view(ViewHandle(2)).unwrap().rect.width, 100.0);
+        assert_eq!(ws.payload(ViewHandle(3)).unwrap(), "explorer");
+    }
+
+    #[test]
+    fn test_hidden_views_keep_payload() {
+        let mut ws = workspace();
+        ws.save_layout("Code");
+
+        ws.dock_view_with(DockArea::Bottom, ViewHandle(4), "watch".to_owned()).unwrap();
+        ws.save_layout("Debug");
+
+        let diff = ws.switch_layout("Code").unwrap();
+        assert_eq!(diff.hidden, vec![ViewHandle(4)]);
+        assert_eq!(ws.view(ViewHandle(4)).is_none(), true);
+        assert_eq!(ws.hidden_views().next().unwrap().payload(), "watch");
+        assert_eq!(ws.split_top_with(ViewHandle(4), Direction::Vertical, String::new()).is_err(), true);
+
+        let diff = ws.switch_layout("Debug").unwrap();
+        assert_eq!(diff.shown, vec![ViewHandle(4)]);
+        assert_eq!(ws.payload(ViewHandle(4)).unwrap(), "watch");
+
+        // Views removed since a layout was saved are reported and left out
+        ws.remove_view(ViewHandle(2)).unwrap();
+        let diff = ws.switch_layout("Code").unwrap();
+        assert_eq!(diff.missing, vec![ViewHandle(2)]);
+        assert_eq!(ws.split.as_ref().unwrap().direction, Direction::Full);
+        assert_eq!(ws.validate().is_ok(), true);
+
+        ws.show_view(DockArea::Right, ViewHandle(4)).unwrap();
+        assert_eq!(ws.area_of(ViewHandle(4)), Some(DockArea::Right));
+
+        match ws.switch_layout("Profile") {
+            Err(Error::LayoutNotFound(ref name)) if name == "Profile" => (),
+            _ => panic!("expected LayoutNotFound"),
+        }
+    }
+
+    #[test]
+    fn test_floating_views_are_docked_again() {
+        let mut ws = workspace();
+        ws.save_layout("Code");
+
+        ws.float_view(ViewHandle(2), Rect::new(100.0, 100.0, 300.0, 200.0)).unwrap();
+        assert_eq!(ws.layout_diff("Code").unwrap(), LayoutDiff::default());
+
+        let diff = ws.switch_layout("Code").unwrap();
+        assert_eq!(diff, LayoutDiff::default());
+        assert_eq!(ws.is_floating(ViewHandle(2)), false);
+        assert_eq!(ws.floating_windows().count(), 0);
+        assert_eq!(ws.area_of(ViewHandle(2)), Some(DockArea::Center));
+        assert_eq!(ws.payload(ViewHandle(2)).unwrap(), "output");
+        assert_eq!(ws.validate().is_ok(), true);
+    }
+
+    #[test]
+    fn test_floating_views_are_hidden() {
+        let mut ws = workspace();
+        ws.remove_view(ViewHandle(2)).unwrap();
+        ws.save_layout("Code");
+
+        ws.split_by_view_handle_with(Direction::Horizontal, ViewHandle(1), ViewHandle(4), "watch".to_owned()).unwrap();
+        ws.float_view(ViewHandle(4), Rect::new(100.0, 100.0, 300.0, 200.0)).unwrap();
+        assert_eq!(ws.layout_diff("Code").unwrap().hidden, vec![ViewHandle(4)]);
+
+        let diff = ws.switch_layout("Code").unwrap();
+        assert_eq!(diff.hidden, vec![ViewHandle(4)]);
+        assert_eq!(ws.view(ViewHandle(4)).is_none(), true);
+        assert_eq!(ws.floating_windows().count(), 0);
+        assert_eq!(ws.hidden_views().map(|view| view.handle).collect::<Vec<_>>(), vec![ViewHandle(4)]);
+        assert_eq!(ws.validate().is_ok(), true);
+
+        ws.show_view(DockArea::Right, ViewHandle(4)).unwrap();
+        assert_eq!(ws.payload(ViewHandle(4)).unwrap(), "watch");
+    }
+}
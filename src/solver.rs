@@ -0,0 +1,215 @@
+//!
+//! A small dense two-phase simplex solver for the constraint layout. Problems are tiny compared to
+//! what general solvers are built for, a few hundred variables at most, so the tableau is kept as
+//! plain vectors.
+//!
+
+const EPSILON: f64 = 1e-9;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Op {
+    Le,
+    Ge,
+    Eq,
+}
+
+/// Sum of `coeffs` (variable, factor) compared with `rhs`
+#[derive(Debug, Clone)]
+pub(crate) struct Row {
+    pub coeffs: Vec<(usize, f64)>,
+    pub op: Op,
+    pub rhs: f64,
+}
+
+struct Tableau {
+    rows: Vec<Vec<f64>>,
+    basis: Vec<usize>,
+    /// Reduced costs, the last entry is the negated value of the objective
+    objective: Vec<f64>,
+}
+
+impl Tableau {
+    fn rhs(&self) -> usize {
+        self.objective.len() - 1
+    }
+
+    fn pivot(&mut self, row: usize, column: usize) {
+        let factor = self.rows[row][column];
+        self.rows[row].iter_mut().for_each(|value| *value /= factor);
+
+        let pivot_row = self.rows[row].clone();
+
+        let eliminate = |target: &mut Vec<f64>| {
+            let factor = target[column];
+
+            if factor.abs() > EPSILON {
+                target.iter_mut().zip(pivot_row.iter()).for_each(|(value, pivot)| *value -= factor * pivot);
+            }
+        };
+
+        for (index, other) in self.rows.iter_mut().enumerate() {
+            if index != row {
+                eliminate(other);
+            }
+        }
+
+        eliminate(&mut self.objective);
+        self.basis[row] = column;
+    }
+
+    /// Runs the simplex with the first `columns` columns allowed to enter. False if it's unbounded
+    /// or doesn't settle
+    fn run(&mut self, columns: usize) -> bool {
+        let rhs = self.rhs();
+        let max_iterations = 50 * (self.rows.len() + rhs);
+
+        for _ in 0..max_iterations {
+            let entering = (0..columns)
+                .filter(|&column| self.objective[column] < -EPSILON)
+                .min_by(|&a, &b| self.objective[a].total_cmp(&self.objective[b]));
+
+            let column = match entering {
+                Some(column) => column,
+                None => return true,
+            };
+
+            let leaving = (0..self.rows.len())
+                .filter(|&row| self.rows[row][column] > EPSILON)
+                .min_by(|&a, &b| {
+                    let ratio = |row: usize| self.rows[row][rhs] / self.rows[row][column];
+                    ratio(a).total_cmp(&ratio(b)).then(self.basis[a].cmp(&self.basis[b]))
+                });
+
+            match leaving {
+                Some(row) => self.pivot(row, column),
+                None => return false,
+            }
+        }
+
+        false
+    }
+}
+
+///
+/// Minimizes `cost` over the variables, which are all >= 0, subject to the rows. Returns the value
+/// of every variable or None if the rows can't all be met.
+///
+pub(crate) fn minimize(vars: usize, cost: &[f64], rows: &[Row]) -> Option<Vec<f64>> {
+    let slacks = rows.iter().filter(|row| row.op != Op::Eq).count();
+    // Rows are flipped to get a positive right hand side, a <= row then needs an artificial variable
+    let needs_artificial = |row: &Row| match row.op {
+        Op::Le => row.rhs < 0.0,
+        Op::Ge => row.rhs >= 0.0,
+        Op::Eq => true,
+    };
+    let artificials = rows.iter().filter(|row| needs_artificial(row)).count();
+
+    let artificial_start = vars + slacks;
+    let width = artificial_start + artificials + 1;
+
+    let mut tableau = Tableau {
+        rows: Vec::with_capacity(rows.len()),
+        basis: Vec::with_capacity(rows.len()),
+        objective: vec![0.0; width],
+    };
+
+    let (mut slack, mut artificial) = (vars, artificial_start);
+
+    for row in rows {
+        let mut values = vec![0.0; width];
+        let sign = if row.rhs < 0.0 { -1.0 } else { 1.0 };
+
+        for &(var, factor) in &row.coeffs {
+            values[var] += factor * sign;
+        }
+
+        values[width - 1] = row.rhs * sign;
+
+        match row.op {
+            Op::Le => { values[slack] = sign; slack += 1; }
+            Op::Ge => { values[slack] = -sign; slack += 1; }
+            Op::Eq => (),
+        }
+
+        if needs_artificial(row) {
+            values[artificial] = 1.0;
+            tableau.basis.push(artificial);
+            artificial += 1;
+
+            // Phase one minimizes the sum of the artificial variables
+            tableau.objective.iter_mut().zip(values.iter()).for_each(|(value, row)| *value -= row);
+            tableau.objective[artificial - 1] = 0.0;
+        } else {
+            tableau.basis.push(slack - 1);
+        }
+
+        tableau.rows.push(values);
+    }
+
+    let rhs = width - 1;
+    let scale = rows.iter().fold(1.0f64, |scale, row| scale.max(row.rhs.abs()));
+
+    if !tableau.run(width - 1) || -tableau.objective[rhs] > 1e-7 * scale {
+        return None;
+    }
+
+    // Pivot artificial variables that are still in the basis (at zero) out where possible
+    for row in 0..tableau.rows.len() {
+        if tableau.basis[row] >= artificial_start {
+            if let Some(column) = (0..artificial_start).find(|&column| tableau.rows[row][column].abs() > EPSILON) {
+                tableau.pivot(row, column);
+            }
+        }
+    }
+
+    tableau.objective = vec![0.0; width];
+    tableau.objective[..vars].copy_from_slice(cost);
+
+    for row in 0..tableau.rows.len() {
+        let basic = tableau.basis[row];
+        let factor = if basic < vars { cost[basic] } else { 0.0 };
+
+        if factor != 0.0 {
+            let values = &tableau.rows[row];
+            tableau.objective.iter_mut().zip(values.iter()).for_each(|(value, row)| *value -= factor * row);
+        }
+    }
+
+    if !tableau.run(artificial_start) {
+        return None;
+    }
+
+    let mut result = vec![0.0; vars];
+
+    for (row, &basic) in tableau.basis.iter().enumerate() {
+        if basic < vars {
+            result[basic] = tableau.rows[row][rhs];
+        }
+    }
+
+    Some(result)
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod test {
+    use super::{minimize, Op, Row};
+    use test_util::check_range;
+
+    #[test]
+    fn test_minimize() {
+        // x + y = 10, x >= 3, minimize 2x + y
+        let rows = [
+            Row { coeffs: vec![(0, 1.0), (1, 1.0)], op: Op::Eq, rhs: 10.0 },
+            Row { coeffs: vec![(0, 1.0)], op: Op::Ge, rhs: 3.0 },
+        ];
+
+        let result = minimize(2, &[2.0, 1.0], &rows).unwrap();
+        assert_eq!(check_range(result[0], 3.0, 1e-6), true);
+        assert_eq!(check_range(result[1], 7.0, 1e-6), true);
+
+        // x <= -1 can't be met with x >= 0
+        let rows = [Row { coeffs: vec![(0, 1.0)], op: Op::Le, rhs: -1.0 }];
+        assert_eq!(minimize(1, &[1.0], &rows).is_none(), true);
+    }
+}
@@ -0,0 +1,127 @@
+use {Rect, Workspace};
+
+///
+/// Metrics used for layout and hit-testing. All values are in logical units and multiplied with
+/// the scale factor of the workspace before use.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Style {
+    /// Thickness of the sizer between two panels
+    pub sizer_size: f32,
+    /// Extra space on both sides of a sizer that still counts as hovering it, see `Workspace::sizer_at`
+    pub sizer_hit_padding: f32,
+    /// Space between the edge of the workspace and the panels
+    pub border: f32,
+    /// Height of the tab bar of a container with more than one view
+    pub tab_bar_height: f32,
+    /// Height of the title bar of a container with a single view
+    pub title_bar_height: f32,
+    /// Minimum width/height of a panel that dragging and resizing tries to keep
+    pub min_panel_size: f32,
+    /// Size of the drop zones along the edges of a container when docking a view
+    pub drop_zone_size: f32,
+    /// Size of the drop zones along the edges of the workspace
+    pub edge_drop_zone_size: f32,
+    /// Distance a tab has to be dragged before it's torn off into a floating window
+    pub tear_off_threshold: f32,
+}
+
+impl Default for Style {
+    fn default() -> Style {
+        Style {
+            sizer_size: 8.0,
+            sizer_hit_padding: 2.0,
+            border: 0.0,
+            tab_bar_height: 24.0,
+            title_bar_height: 20.0,
+            min_panel_size: 32.0,
+            drop_zone_size: 64.0,
+            edge_drop_zone_size: 32.0,
+            tear_off_threshold: 8.0,
+        }
+    }
+}
+
+impl Style {
+    /// Returns the style with all metrics multiplied by the scale factor
+    pub fn scaled(&self, scale: f32) -> Style {
+        Style {
+            sizer_size: self.sizer_size * scale,
+            sizer_hit_padding: self.sizer_hit_padding * scale,
+            border: self.border * scale,
+            tab_bar_height: self.tab_bar_height * scale,
+            title_bar_height: self.title_bar_height * scale,
+            min_panel_size: self.min_panel_size * scale,
+            drop_zone_size: self.drop_zone_size * scale,
+            edge_drop_zone_size: self.edge_drop_zone_size * scale,
+            tear_off_threshold: self.tear_off_threshold * scale,
+        }
+    }
+}
+
+impl<T> Workspace<T> {
+    /// The style in physical pixels
+    pub fn metrics(&self) -> Style {
+        self.style.scaled(self.scale)
+    }
+
+    ///
+    /// The part of `rect` that is used for panels, that is the rect without the border. The border
+    /// is made smaller if it doesn't leave any space.
+    ///
+    pub fn content_rect(&self) -> Rect {
+        let rect = self.rect;
+        let border = (self.style.border * self.scale)
+            .min((rect.width.min(rect.height) - 1.0) * 0.5)
+            .max(0.0);
+
+        Rect::new(rect.x + border, rect.y + border, rect.width - border * 2.0, rect.height - border * 2.0)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod test {
+    use test_util::check_range;
+    use {Direction, Rect, ViewHandle, Workspace};
+
+    #[test]
+    fn test_border() {
+        let mut ws: Workspace = Workspace::new(Rect::new(0.0, 0.0, 1000.0, 500.0)).unwrap();
+        ws.style.border = 10.0;
+        ws.split_top(ViewHandle(1), Direction::Vertical).unwrap();
+        ws.split_top(ViewHandle(2), Direction::Vertical).unwrap();
+        ws.update();
+
+        let split = ws.split.as_ref().unwrap();
+        assert_eq!(check_range(split.left_views().rect.x, 10.0, 0.01), true);
+        assert_eq!(check_range(split.left_views().rect.width, 490.0, 0.01), true);
+        assert_eq!(check_range(split.right_views().rect.height, 480.0, 0.01), true);
+        assert_eq!(ws.validate().is_ok(), true);
+    }
+
+    #[test]
+    fn test_sizer_metrics() {
+        let mut ws: Workspace = Workspace::new(Rect::new(0.0, 0.0, 1000.0, 1000.0)).unwrap();
+        ws.split_top(ViewHandle(1), Direction::Vertical).unwrap();
+        ws.split_top(ViewHandle(2), Direction::Vertical).unwrap();
+        ws.update();
+
+        // Sizer starts at 500, hit padding reaches to 498
+        assert_eq!(ws.sizer_at((498.5, 100.0)).is_some(), true);
+        assert_eq!(ws.is_hovering_sizer((498.5, 100.0)).is_none(), true);
+        assert_eq!(ws.is_hovering_sizer((507.5, 100.0)).is_some(), true);
+
+        ws.style.sizer_hit_padding = 0.0;
+        assert_eq!(ws.sizer_at((498.5, 100.0)).is_none(), true);
+
+        ws.style.sizer_size = 4.0;
+        assert_eq!(ws.is_hovering_sizer((507.5, 100.0)).is_none(), true);
+
+        // Dragging stops at the minimum panel size
+        let handle = ws.split.as_ref().unwrap().handle;
+        ws.style.min_panel_size = 100.0;
+        ws.drag_sizer(handle, (1000.0, 0.0)).unwrap();
+        assert_eq!(check_range(ws.split.as_ref().unwrap().ratio(), 0.1, 0.001), true);
+    }
+}
@@ -0,0 +1,28 @@
+//!
+//! Helpers shared by the unit tests of the modules.
+//!
+
+use {view, Layout, Rect, ViewHandle, Workspace};
+
+/// True if `inv` is less than `delta` away from `value`
+pub(crate) fn check_range<F: Into<f64>>(inv: F, value: F, delta: F) -> bool {
+    (inv.into() - value.into()).abs() < delta.into()
+}
+
+/// Views 1 and 2 side by side in a 1000x600 workspace
+pub(crate) fn workspace() -> Workspace {
+    let layout = Layout::vertical(0.5).left(view(1)).right(view(2));
+    Workspace::from_layout(Rect::new(0.0, 0.0, 1000.0, 600.0), layout).unwrap()
+}
+
+/// View 1 on the left of views 2 and 3 on top of each other in a 1000x600 workspace
+pub(crate) fn nested_workspace() -> Workspace {
+    let layout = Layout::vertical(0.5).left(view(1)).right(Layout::horizontal(0.5).top(view(2)).bottom(view(3)));
+    Workspace::from_layout(Rect::new(0.0, 0.0, 1000.0, 600.0), layout).unwrap()
+}
+
+/// The handles as numbers in ascending order, for comparing the views reported by `update`
+pub(crate) fn sorted(mut handles: Vec<ViewHandle>) -> Vec<u64> {
+    handles.sort_by_key(|handle| handle.0);
+    handles.into_iter().map(|handle| handle.0).collect()
+}
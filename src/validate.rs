@@ -0,0 +1,295 @@
+use std::collections::HashSet;
+use {Container, Direction, DockArea, Error, Rect, Result, Split, SplitChild, SplitHandle, TreeError, ViewHandle, Workspace};
+
+/// Rects closer than this (in pixels) are treated as touching rather than overlapping
+const OVERLAP_EPSILON: f32 = 0.01;
+
+/// Relative tolerance used when comparing the covered area against the workspace area
+const AREA_EPSILON: f32 = 0.0001;
+
+/// Allowed difference between the sum of the weights of a split and 1.0
+const WEIGHT_EPSILON: f32 = 0.001;
+
+/// A leaf container together with the rect it gets when the tree is laid out
+struct Leaf {
+    split: SplitHandle,
+    first_view: ViewHandle,
+    rect: Rect,
+}
+
+struct Validator {
+    views: HashSet<ViewHandle>,
+    splits: HashSet<SplitHandle>,
+    leaves: Vec<Leaf>,
+}
+
+impl Validator {
+    fn new() -> Validator {
+        Validator {
+            views: HashSet::new(),
+            splits: HashSet::new(),
+            leaves: Vec::new(),
+        }
+    }
+
+    fn check_container<T>(&mut self, container: &Container<T>) -> Result<()> {
+        for view in &container.views {
+            if !self.views.insert(view.handle) {
+                return Err(TreeError::DuplicateViewHandle(view.handle).into());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_child<T>(&mut self, parent: &Split<T>, child: &SplitChild<T>, rect: Rect) -> Result<()> {
+        self.check_container(&child.views)?;
+
+        match child.split {
+            Some(ref split) => {
+                if !child.views.views.is_empty() {
+                    return Err(TreeError::AmbiguousSide(parent.handle).into());
+                }
+
+                self.check_split(split, rect, false)
+            }
+
+            None => {
+                if child.views.views.is_empty() {
+                    return Err(TreeError::EmptyContainer(parent.handle).into());
+                }
+
+                self.leaves.push(Leaf {
+                    split: parent.handle,
+                    first_view: child.views.views[0].handle,
+                    rect,
+                });
+
+                Ok(())
+            }
+        }
+    }
+
+    fn check_split<T>(&mut self, split: &Split<T>, rect: Rect, is_root: bool) -> Result<()> {
+        if !self.splits.insert(split.handle) {
+            return Err(TreeError::DuplicateSplitHandle(split.handle).into());
+        }
+
+        if let Direction::Full = split.direction {
+            if !is_root || split.left().is_some() || split.children[1..].iter().any(|child| !child.is_empty()) {
+                return Err(TreeError::MisplacedFullSplit(split.handle).into());
+            }
+
+            if split.left_views().views.is_empty() {
+                return Err(TreeError::EmptyContainer(split.handle).into());
+            }
+
+            self.check_container(split.left_views())?;
+
+            self.leaves.push(Leaf {
+                split: split.handle,
+                first_view: split.left_views().views[0].handle,
+                rect,
+            });
+
+            return Ok(());
+        }
+
+        if split.children.len() < 2 {
+            return Err(TreeError::InvalidRatio(split.handle, 1.0).into());
+        }
+
+        if let Some(child) = split.children.iter().find(|child| !(child.weight > 0.0 && child.weight < 1.0)) {
+            return Err(TreeError::InvalidRatio(split.handle, child.weight).into());
+        }
+
+        let total: f32 = split.children.iter().map(|child| child.weight).sum();
+
+        if (total - 1.0).abs() > WEIGHT_EPSILON {
+            return Err(TreeError::InvalidRatio(split.handle, total).into());
+        }
+
+        let rects = split.child_rects(rect, false);
+
+        for (child, rect) in split.children.iter().zip(rects) {
+            self.check_child(split, child, rect)?;
+        }
+
+        Ok(())
+    }
+
+    fn check_tiling(&self, rect: Rect) -> Result<()> {
+        let mut covered = 0.0;
+
+        for (index, leaf) in self.leaves.iter().enumerate() {
+            if !leaf.rect.width.is_finite() || !leaf.rect.height.is_finite() ||
+               leaf.rect.width < 0.0 || leaf.rect.height < 0.0 {
+                return Err(TreeError::InvalidRect(leaf.split, leaf.rect).into());
+            }
+
+            if leaf.rect.x < rect.x - OVERLAP_EPSILON ||
+               leaf.rect.y < rect.y - OVERLAP_EPSILON ||
+               leaf.rect.x + leaf.rect.width > rect.x + rect.width + OVERLAP_EPSILON ||
+               leaf.rect.y + leaf.rect.height > rect.y + rect.height + OVERLAP_EPSILON {
+                return Err(TreeError::InvalidRect(leaf.split, leaf.rect).into());
+            }
+
+            for other in &self.leaves[index + 1..] {
+                if is_overlapping(leaf.rect, other.rect) {
+                    return Err(TreeError::OverlappingRects(leaf.first_view, other.first_view).into());
+                }
+            }
+
+            covered += leaf.rect.width * leaf.rect.height;
+        }
+
+        let expected = rect.width * rect.height;
+
+        if (covered - expected).abs() > expected * AREA_EPSILON {
+            return Err(TreeError::IncompleteTiling { covered, expected }.into());
+        }
+
+        Ok(())
+    }
+}
+
+fn is_overlapping(a: Rect, b: Rect) -> bool {
+    let width = (a.x + a.width).min(b.x + b.width) - a.x.max(b.x);
+    let height = (a.y + a.height).min(b.y + b.height) - a.y.max(b.y);
+
+    width > OVERLAP_EPSILON && height > OVERLAP_EPSILON
+}
+
+impl<T> Workspace<T> {
+    ///
+    /// Check that the split tree is consistent: view and split handles are unique, every leaf
+    /// container holds at least one view, weights are within (0, 1) and sum up to 1, `Direction::Full` is only used
+    /// by a root holding a single container and the leaf rects of each tree tile the rect of its
+    /// `DockArea` without overlapping. View handles of floating windows and hidden views count
+    /// towards the uniqueness too. Rects are computed from the current weights so `update` doesn't
+    /// need to be called first.
+    ///
+    pub fn validate(&self) -> Result<()> {
+        let mut validator = Validator::new();
+
+        let rects = self.area_rects();
+
+        for (area, rect) in DockArea::ALL.iter().zip(rects.iter()) {
+            if let Some(ref split) = *self.tree(*area) {
+                validator.leaves.clear();
+                validator.check_split(split, *rect, true)?;
+                validator.check_tiling(*rect)?;
+            }
+        }
+
+        // Floating and hidden views share the handle space with the docked ones
+        for window in &self.floating {
+            validator.check_container(&window.container)?;
+        }
+
+        for view in &self.hidden {
+            if !validator.views.insert(view.handle) {
+                return Err(TreeError::DuplicateViewHandle(view.handle).into());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validates the tree after a mutation. Only done in debug builds as it walks the whole tree.
+    pub(crate) fn debug_validate(&self) {
+        if cfg!(debug_assertions) {
+            if let Err(Error::InvalidTree(err)) = self.validate() {
+                panic!("viewdock: workspace is invalid after mutation: {}", err);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod test {
+    use {Container, Direction, Error, FloatingWindow, Rect, Split, SplitHandle, TreeError, View, ViewHandle, Workspace};
+
+    fn workspace() -> Workspace {
+        let mut ws: Workspace = Workspace::new(Rect::new(0.0, 0.0, 1024.0, 1024.0)).unwrap();
+        ws.split_top(ViewHandle(1), Direction::Vertical).unwrap();
+        ws.split_top(ViewHandle(2), Direction::Vertical).unwrap();
+        ws.split_by_view_handle(Direction::Horizontal, ViewHandle(2), ViewHandle(3)).unwrap();
+        ws.split_top(ViewHandle(4), Direction::Horizontal).unwrap();
+        ws
+    }
+
+    #[test]
+    fn test_validate_ok() {
+        assert_eq!(workspace().validate().is_ok(), true);
+    }
+
+    #[test]
+    fn test_validate_duplicate_view() {
+        let mut ws = workspace();
+        ws.split.as_mut().unwrap().right_views_mut().views.push(View::new(ViewHandle(1), ()));
+
+        match ws.validate() {
+            Err(Error::InvalidTree(TreeError::DuplicateViewHandle(ViewHandle(1)))) => (),
+            res => panic!("unexpected result {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_validate_duplicate_outside_tree() {
+        let mut ws = workspace();
+        let mut container = Container::new();
+        container.views.push(View::new(ViewHandle(2), ()));
+        ws.floating.push(FloatingWindow { container, rect: Rect::new(0.0, 0.0, 100.0, 100.0) });
+
+        match ws.validate() {
+            Err(Error::InvalidTree(TreeError::DuplicateViewHandle(ViewHandle(2)))) => (),
+            res => panic!("unexpected result {:?}", res),
+        }
+
+        ws.floating.clear();
+        ws.hidden.push(View::new(ViewHandle(3), ()));
+
+        match ws.validate() {
+            Err(Error::InvalidTree(TreeError::DuplicateViewHandle(ViewHandle(3)))) => (),
+            res => panic!("unexpected result {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_validate_invalid_ratio() {
+        let mut ws = workspace();
+        ws.split.as_mut().unwrap().set_ratio(1.5);
+
+        match ws.validate() {
+            Err(Error::InvalidTree(TreeError::InvalidRatio(SplitHandle(2), _))) => (),
+            res => panic!("unexpected result {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_validate_full_not_at_root() {
+        let mut ws = workspace();
+        let mut child = Box::new(Split::new(Direction::Full, SplitHandle(100)));
+        child.left_views_mut().views.push(View::new(ViewHandle(100), ()));
+        *ws.split.as_mut().unwrap().left_mut() = Some(child);
+        ws.split.as_mut().unwrap().left_views_mut().views.clear();
+
+        match ws.validate() {
+            Err(Error::InvalidTree(TreeError::MisplacedFullSplit(SplitHandle(100)))) => (),
+            res => panic!("unexpected result {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_validate_empty_leaf() {
+        let mut ws = workspace();
+        ws.split.as_mut().unwrap().right_mut().as_mut().unwrap().right_views_mut().views.clear();
+
+        match ws.validate() {
+            Err(Error::InvalidTree(TreeError::EmptyContainer(_))) => (),
+            res => panic!("unexpected result {:?}", res),
+        }
+    }
+}
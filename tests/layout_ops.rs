@@ -0,0 +1,77 @@
+extern crate viewdock;
+
+mod ops;
+
+use ops::{decode, Harness};
+
+/// Small xorshift generator so the tests are reproducible without extra dependencies
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn bytes(&mut self, count: usize) -> Vec<u8> {
+        (0..count).map(|_| self.next() as u8).collect()
+    }
+}
+
+fn run(data: &[u8]) {
+    let mut harness = Harness::new();
+
+    for op in decode(data) {
+        harness.apply(op);
+        harness.check();
+    }
+}
+
+#[test]
+fn test_random_operations() {
+    for seed in 1..200u64 {
+        let mut rng = Rng(seed.wrapping_mul(0x9e37_79b9_7f4a_7c15));
+        let data = rng.bytes(384);
+        run(&data);
+    }
+}
+
+#[test]
+fn test_build_and_tear_down() {
+    // Mostly splits followed by mostly removes so deep trees get collapsed again
+    let mut rng = Rng(0x1234_5678);
+    let mut data = Vec::new();
+
+    for _ in 0..64 {
+        data.push(1);
+        data.extend(rng.bytes(2));
+    }
+
+    for _ in 0..64 {
+        data.push(2);
+        data.extend(rng.bytes(1));
+    }
+
+    run(&data);
+}
+
+#[test]
+fn test_dock_zones() {
+    // Docks into every area, moves views between the zones and resizes them
+    let mut rng = Rng(0x8765_4321);
+    let mut data = Vec::new();
+
+    for _ in 0..96 {
+        data.push([9, 10, 11, 3][rng.next() as usize % 4]);
+        data.extend(rng.bytes(3));
+    }
+
+    run(&data);
+}
+
+#[test]
+fn test_empty_input() {
+    run(&[]);
+}
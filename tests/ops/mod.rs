@@ -0,0 +1,296 @@
+//! Random layout operations shared by the randomized tests and the fuzz target. Operations are
+//! decoded from raw bytes so any byte sequence maps to a valid sequence of user actions.
+
+use std::collections::HashSet;
+use std::error::Error;
+use viewdock::{Direction, DockArea, Rect, ResizeStrategy, Split, SplitHandle, ViewHandle, Workspace};
+
+#[derive(Debug, Clone, Copy)]
+pub enum Op {
+    SplitTop(Direction),
+    SplitByView(Direction, u8),
+    SplitByViewN(Direction, u8, u8),
+    Remove(u8),
+    Move(Direction, u8, u8),
+    Drag(u8, i8, i8),
+    Resize(u8, u8, u8),
+    Rotate(u8),
+    Swap(u8),
+    Dock(DockArea),
+    MoveToDock(u8, DockArea),
+    ZoneSize(DockArea, u8),
+    Update,
+}
+
+fn direction(byte: u8) -> Direction {
+    if byte & 1 == 0 {
+        Direction::Vertical
+    } else {
+        Direction::Horizontal
+    }
+}
+
+fn area(byte: u8) -> DockArea {
+    DockArea::ALL[byte as usize % DockArea::ALL.len()]
+}
+
+/// Decodes bytes into operations. Indices are resolved against the current tree when applied.
+pub fn decode(data: &[u8]) -> Vec<Op> {
+    let mut ops = Vec::new();
+    let mut iter = data.iter().cloned();
+
+    while let Some(code) = iter.next() {
+        let mut arg = || iter.next().unwrap_or(0);
+
+        let op = match code % 13 {
+            0 => Op::SplitTop(direction(arg())),
+            1 => Op::SplitByView(direction(arg()), arg()),
+            2 => Op::Remove(arg()),
+            3 => Op::Move(direction(arg()), arg(), arg()),
+            4 => Op::Drag(arg(), arg() as i8, arg() as i8),
+            5 => Op::Resize(arg(), arg(), arg()),
+            6 => Op::SplitByViewN(direction(arg()), arg(), arg()),
+            7 => Op::Rotate(arg()),
+            8 => Op::Swap(arg()),
+            9 => Op::Dock(area(arg())),
+            10 => Op::MoveToDock(arg(), area(arg())),
+            11 => Op::ZoneSize(area(arg()), arg()),
+            _ => Op::Update,
+        };
+
+        ops.push(op);
+    }
+
+    ops
+}
+
+fn collect_split(split: &Split, views: &mut Vec<ViewHandle>, splits: &mut Vec<SplitHandle>) {
+    splits.push(split.handle);
+
+    for child in &split.children {
+        views.extend(child.views.views.iter().map(|view| view.handle));
+
+        if let Some(ref split) = child.split {
+            collect_split(split, views, splits);
+        }
+    }
+}
+
+/// Root of the tree of the area
+fn tree(ws: &Workspace, area: DockArea) -> Option<&Split> {
+    match ws.dock_zone(area) {
+        Some(zone) => zone.split.as_deref(),
+        None => ws.split.as_deref(),
+    }
+}
+
+/// Returns all view and split handles in the trees of all areas
+pub fn collect(ws: &Workspace) -> (Vec<ViewHandle>, Vec<SplitHandle>) {
+    let mut views = Vec::new();
+    let mut splits = Vec::new();
+
+    for &area in &DockArea::ALL {
+        if let Some(split) = tree(ws, area) {
+            collect_split(split, &mut views, &mut splits);
+        }
+    }
+
+    (views, splits)
+}
+
+/// Applies operations to a workspace and tracks which views it is expected to contain
+pub struct Harness {
+    pub ws: Workspace,
+    pub expected: HashSet<ViewHandle>,
+    next_view: u64,
+}
+
+impl Harness {
+    pub fn new() -> Harness {
+        Harness {
+            ws: Workspace::new(Rect::new(0.0, 0.0, 1024.0, 768.0)).unwrap(),
+            expected: HashSet::new(),
+            next_view: 1,
+        }
+    }
+
+    fn new_view(&mut self) -> ViewHandle {
+        let handle = ViewHandle(self.next_view);
+        self.next_view += 1;
+        handle
+    }
+
+    fn pick<T: Copy>(items: &[T], index: u8) -> Option<T> {
+        if items.is_empty() {
+            None
+        } else {
+            Some(items[index as usize % items.len()])
+        }
+    }
+
+    pub fn apply(&mut self, op: Op) {
+        let (views, splits) = collect(&self.ws);
+
+        match op {
+            Op::SplitTop(direction) => {
+                let handle = self.new_view();
+                self.ws.split_top(handle, direction).unwrap();
+                self.expected.insert(handle);
+            }
+
+            Op::SplitByView(direction, target) => {
+                if let Some(target) = Self::pick(&views, target) {
+                    let handle = self.new_view();
+                    self.ws.split_by_view_handle(direction, target, handle).unwrap();
+                    self.expected.insert(handle);
+                }
+            }
+
+            Op::SplitByViewN(direction, target, count) => {
+                if let Some(target) = Self::pick(&views, target) {
+                    let handles = (0..count % 4).map(|_| self.new_view()).collect::<Vec<ViewHandle>>();
+                    self.ws.split_by_view_handle_n(direction, target, &handles).unwrap();
+                    self.expected.extend(handles);
+                }
+            }
+
+            Op::Remove(index) => {
+                if let Some(handle) = Self::pick(&views, index) {
+                    self.ws.remove_view(handle).unwrap();
+                    self.expected.remove(&handle);
+                }
+            }
+
+            Op::Move(direction, index, target) => {
+                if let (Some(handle), Some(target)) = (Self::pick(&views, index), Self::pick(&views, target)) {
+                    assert_eq!(self.ws.move_view(direction, target, handle).is_ok(), handle != target);
+                }
+            }
+
+            Op::Drag(index, dx, dy) => {
+                if let Some(handle) = Self::pick(&splits, index) {
+                    self.ws.drag_sizer(handle, (dx as f32, dy as f32)).unwrap();
+                }
+            }
+
+            Op::Rotate(index) => {
+                if let Some(handle) = Self::pick(&splits, index) {
+                    // The root is Full when it holds a single view and can't be rotated
+                    let _ = self.ws.rotate_split(handle);
+                }
+            }
+
+            Op::Swap(index) => {
+                if let Some(handle) = Self::pick(&splits, index) {
+                    let _ = self.ws.swap_split(handle);
+                }
+            }
+
+            Op::Dock(area) => {
+                let handle = self.new_view();
+                self.ws.dock_view(area, handle).unwrap();
+                self.expected.insert(handle);
+            }
+
+            Op::MoveToDock(index, area) => {
+                if let Some(handle) = Self::pick(&views, index) {
+                    self.ws.move_to_dock(area, handle).unwrap();
+                }
+            }
+
+            Op::ZoneSize(area, size) => {
+                if let Some(zone) = self.ws.dock_zone_mut(area) {
+                    zone.size = size as f32 * 4.0;
+                }
+            }
+
+            Op::Resize(width, height, strategy) => {
+                let strategy = match strategy % 3 {
+                    0 => ResizeStrategy::Proportional,
+                    1 => ResizeStrategy::KeepFixed,
+                    _ => ResizeStrategy::KeepView(Self::pick(&views, strategy).unwrap_or(ViewHandle(0))),
+                };
+
+                let rect = Rect::new(0.0, 0.0, 64.0 + width as f32 * 8.0, 64.0 + height as f32 * 8.0);
+                self.ws.set_rect(rect, strategy).unwrap();
+            }
+
+            Op::Update => (),
+        }
+
+        self.ws.update();
+    }
+
+    /// Panics if the workspace is inconsistent with itself or with the applied operations
+    pub fn check(&self) {
+        if let Err(err) = self.ws.validate() {
+            let reason = err.source().map(|source| source.to_string()).unwrap_or_default();
+            panic!("invalid workspace: {} {}\n{}", err, reason, self.ws.to_dot());
+        }
+
+        let (views, splits) = collect(&self.ws);
+
+        let unique_views = views.iter().cloned().collect::<HashSet<ViewHandle>>();
+        let unique_splits = splits.iter().cloned().collect::<HashSet<SplitHandle>>();
+
+        assert_eq!(unique_views.len(), views.len(), "duplicate view handles");
+        assert_eq!(unique_splits.len(), splits.len(), "duplicate split handles");
+        assert_eq!(unique_views, self.expected, "views in tree doesn't match applied operations");
+
+        // The areas tile the workspace and the leaves of each tree tile its area
+        let mut total = 0.0;
+
+        for &area in &DockArea::ALL {
+            let rect = self.ws.area_rect(area);
+            check_rect(rect);
+            total += rect.width * rect.height;
+
+            if let Some(split) = tree(&self.ws, area) {
+                let mut covered = 0.0;
+                check_rects(split, &mut covered);
+
+                let expected = rect.width * rect.height;
+                assert!((covered - expected).abs() <= expected.max(1.0) * 0.0001,
+                        "leaves cover {} of {} in {:?}", covered, expected, area);
+            }
+        }
+
+        let expected = self.ws.rect().width * self.ws.rect().height;
+        assert!((total - expected).abs() <= expected * 0.0001, "areas cover {} of {}", total, expected);
+    }
+}
+
+fn check_rect(rect: Rect) {
+    assert!(rect.x.is_finite() && rect.y.is_finite(), "non-finite position {:?}", rect);
+    assert!(rect.width.is_finite() && rect.height.is_finite(), "non-finite size {:?}", rect);
+    assert!(rect.width >= 0.0 && rect.height >= 0.0, "negative size {:?}", rect);
+}
+
+/// Checks the rects computed by `update` and sums up the area of all leaves
+fn check_rects(split: &Split, area: &mut f32) {
+    for child in &split.children {
+        let container = &child.views;
+
+        match child.split {
+            Some(ref split) => check_rects(split, area),
+            None => {
+                for view in &container.views {
+                    check_rect(view.rect);
+                    assert_eq!(view.rect.x, container.rect.x);
+                    assert_eq!(view.rect.width, container.rect.width);
+                    assert_eq!((view.rect.y, view.rect.height), (container.content.y, container.content.height));
+                }
+
+                check_rect(container.header);
+                check_rect(container.content);
+                assert!((container.header.height + container.content.height - container.rect.height).abs() < 0.01,
+                        "header and content don't fill {:?}", container.rect);
+
+                if !container.views.is_empty() {
+                    check_rect(container.rect);
+                    *area += container.rect.width * container.rect.height;
+                }
+            }
+        }
+    }
+}